@@ -0,0 +1,51 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use each_cons::ConsGroupExt;
+
+fn inputs<T: Copy>(len: usize, values: &[T]) -> Vec<(usize, Vec<T>)> {
+	[1, 16, 1024]
+		.iter()
+		.map(|&run| (run, (0..len).map(|i| values[i / run % values.len()]).collect()))
+		.collect()
+}
+
+fn bench_u8(c: &mut Criterion) {
+	let mut group = c.benchmark_group("u8");
+	for (run, slice) in inputs(1 << 20, &[0u8, 255]) {
+		group.bench_with_input(BenchmarkId::new("cons_group", run), &slice, |b, slice| {
+			b.iter(|| black_box(slice.cons_group().count()))
+		});
+		group.bench_with_input(BenchmarkId::new("cons_group_fast", run), &slice, |b, slice| {
+			b.iter(|| black_box(slice.cons_group_fast().count()))
+		});
+	}
+	group.finish();
+}
+
+fn bench_i32(c: &mut Criterion) {
+	let mut group = c.benchmark_group("i32");
+	for (run, slice) in inputs(1 << 20, &[-1i32, 0, 1]) {
+		group.bench_with_input(BenchmarkId::new("cons_group", run), &slice, |b, slice| {
+			b.iter(|| black_box(slice.cons_group().count()))
+		});
+		group.bench_with_input(BenchmarkId::new("cons_group_fast", run), &slice, |b, slice| {
+			b.iter(|| black_box(slice.cons_group_fast().count()))
+		});
+	}
+	group.finish();
+}
+
+fn bench_f32(c: &mut Criterion) {
+	let mut group = c.benchmark_group("f32");
+	for (run, slice) in inputs(1 << 20, &[0.5f32, 1.5]) {
+		group.bench_with_input(BenchmarkId::new("chunk_while", run), &slice, |b, slice| {
+			b.iter(|| black_box(slice.chunk_while(|a, b| a == b).count()))
+		});
+		group.bench_with_input(BenchmarkId::new("cons_group_fast", run), &slice, |b, slice| {
+			b.iter(|| black_box(slice.cons_group_fast().count()))
+		});
+	}
+	group.finish();
+}
+
+criterion_group!(benches, bench_u8, bench_i32, bench_f32);
+criterion_main!(benches);
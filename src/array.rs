@@ -0,0 +1,192 @@
+//! Windows of consecutive elements as fixed-size arrays.
+
+use core::convert::TryFrom;
+use core::iter::{Fuse, FusedIterator};
+
+/// Iterator over overlapping `&[T; N]` windows of a slice, see
+/// [`ConsGroupExt::each_cons_array`].
+///
+/// [`ConsGroupExt::each_cons_array`]: crate::ConsGroupExt::each_cons_array
+pub struct ConsArray<'a, T, const N: usize> {
+	remaining: &'a [T],
+}
+
+impl<'a, T, const N: usize> ConsArray<'a, T, N> {
+	pub(crate) fn new(slice: &'a [T]) -> Self {
+		assert!(N != 0, "size must be non-zero");
+		Self {
+			remaining: slice
+		}
+	}
+}
+
+impl<'a, T, const N: usize> Iterator for ConsArray<'a, T, N> {
+	type Item = &'a [T; N];
+	fn next(&mut self) -> Option<Self::Item> {
+		let window = self.remaining.get(..N)?;
+		self.remaining = &self.remaining[1..];
+		Some(<&[T; N]>::try_from(window).unwrap())
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = (self.remaining.len() + 1).saturating_sub(N);
+		(len, Some(len))
+	}
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for ConsArray<'a, T, N> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let start = self.remaining.len().checked_sub(N)?;
+		let window = &self.remaining[start..];
+		self.remaining = &self.remaining[..self.remaining.len() - 1];
+		Some(<&[T; N]>::try_from(window).unwrap())
+	}
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for ConsArray<'a, T, N> {}
+
+impl<'a, T, const N: usize> FusedIterator for ConsArray<'a, T, N> {}
+
+/// Iterator over overlapping `[T; N]` windows of an iterator, see
+/// [`ConsIterator::each_cons_array`].
+///
+/// [`ConsIterator::each_cons_array`]: crate::ConsIterator::each_cons_array
+pub struct ConsArrayIter<I: Iterator, const N: usize> {
+	/// Fused, as filling the first window may read past the end.
+	iter: Fuse<I>,
+	window: Option<[I::Item; N]>,
+	done: bool,
+}
+
+impl<I: Iterator, const N: usize> ConsArrayIter<I, N> {
+	pub(crate) fn new(iter: I) -> Self {
+		assert!(N != 0, "size must be non-zero");
+		Self {
+			iter: iter.fuse(),
+			window: None,
+			done: false,
+		}
+	}
+
+	/// Reads the first `N` items, or fuses the iterator if there are fewer.
+	fn fill(&mut self) -> Option<&mut [I::Item; N]> {
+		let iter = &mut self.iter;
+		let items = [(); N].map(|_| iter.next());
+		if items.iter().any(Option::is_none) {
+			self.done = true;
+			return None;
+		}
+		Some(self.window.insert(items.map(Option::unwrap)))
+	}
+}
+
+impl<I: Iterator, const N: usize> Iterator for ConsArrayIter<I, N>
+where I::Item: Clone {
+	type Item = [I::Item; N];
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done { return None; }
+		let window = match &mut self.window {
+			None => self.fill()?,
+			Some(window) => match self.iter.next() {
+				Some(item) => {
+					window.rotate_left(1);
+					window[N - 1] = item;
+					window
+				}
+				None => {
+					self.done = true;
+					self.window = None;
+					return None;
+				}
+			},
+		};
+		Some(window.clone())
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		if self.done { return (0, Some(0)); }
+		let missing = if self.window.is_some() { 0 } else { N - 1 };
+		let (lower, upper) = self.iter.size_hint();
+		(
+			lower.saturating_sub(missing),
+			upper.map(|upper| upper.saturating_sub(missing)),
+		)
+	}
+}
+
+impl<I: ExactSizeIterator, const N: usize> ExactSizeIterator for ConsArrayIter<I, N>
+where I::Item: Clone {}
+
+impl<I: Iterator, const N: usize> FusedIterator for ConsArrayIter<I, N>
+where I::Item: Clone {}
+
+#[cfg(test)]
+mod tests {
+	use crate::{ConsGroupExt, ConsIterator};
+
+	#[test]
+	fn yields_slice_windows() {
+		let slice = [1, 2, 3, 4, 5];
+		let mut windows = slice.each_cons_array::<3>();
+		assert_eq!(windows.len(), 3);
+		assert_eq!(windows.next(), Some(&[1, 2, 3]));
+		assert_eq!(windows.next_back(), Some(&[3, 4, 5]));
+		assert_eq!(windows.next(), Some(&[2, 3, 4]));
+		assert_eq!(windows.next(), None);
+		assert_eq!(windows.next_back(), None);
+		assert_eq!([1, 2].each_cons_array::<3>().count(), 0);
+	}
+
+	#[test]
+	fn matches_slice_windows() {
+		let slice: Vec<u32> = (0..20).collect();
+		let arrays: Vec<&[u32]> = slice.each_cons_array::<4>().map(|w| &w[..]).collect();
+		assert_eq!(arrays, slice.windows(4).collect::<Vec<_>>());
+		let arrays: Vec<&[u32]> = slice.each_cons_array::<4>().rev().map(|w| &w[..]).collect();
+		assert_eq!(arrays, slice.windows(4).rev().collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn pattern_matches_windows() {
+		let increasing = [1, 3, 2, 5, 6]
+			.each_cons_array()
+			.filter(|[a, b]| a < b)
+			.count();
+		assert_eq!(increasing, 3);
+	}
+
+	#[test]
+	fn yields_iterator_windows() {
+		let mut windows = (1..6).each_cons_array::<3>();
+		assert_eq!(windows.len(), 3);
+		assert_eq!(windows.next(), Some([1, 2, 3]));
+		assert_eq!(windows.len(), 2);
+		assert_eq!(windows.next(), Some([2, 3, 4]));
+		assert_eq!(windows.next(), Some([3, 4, 5]));
+		assert_eq!(windows.next(), None);
+		assert_eq!(windows.len(), 0);
+		assert_eq!((1..3).each_cons_array::<3>().next(), None);
+		assert_eq!((1..3).each_cons_array::<3>().len(), 0);
+	}
+
+	#[test]
+	fn stops_reading_at_the_end() {
+		let mut items = [1].iter().copied();
+		let mut ended = false;
+		let source = core::iter::from_fn(move || {
+			assert!(!ended, "next called after None");
+			let item = items.next();
+			ended = item.is_none();
+			item
+		});
+		let mut windows = source.each_cons_array::<3>();
+		assert_eq!(windows.next(), None);
+		assert_eq!(windows.next(), None);
+	}
+
+	#[test]
+	#[should_panic(expected = "size must be non-zero")]
+	fn rejects_empty_arrays() {
+		[1, 2, 3].each_cons_array::<0>();
+	}
+}
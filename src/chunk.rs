@@ -0,0 +1,125 @@
+//! Port of ruby's `Enumerable#chunk`.
+
+use crate::keyed_run;
+use core::iter::FusedIterator;
+
+/// Key returned by the closure given to [`ConsGroupExt::chunk`].
+///
+/// [`ConsGroupExt::chunk`]: crate::ConsGroupExt::chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Chunk<K> {
+	/// Groups the element with its neighbours having an equal key.
+	Key(K),
+	/// Drops the element, ending the current chunk. This is ruby's `nil` or
+	/// `:_separator`.
+	Separator,
+	/// Puts the element in a chunk of its own, reported under the given key.
+	/// This is ruby's `:_alone`, which has no key of its own.
+	Alone(K),
+}
+
+/// Iterator over keyed chunks of consecutive elements, see
+/// [`ConsGroupExt::chunk`].
+///
+/// [`ConsGroupExt::chunk`]: crate::ConsGroupExt::chunk
+pub struct Chunked<'a, T, K, F> {
+	remaining: &'a [T],
+	key: F,
+	/// Key of the element starting `remaining`, see [`keyed_run`].
+	pending: Option<Chunk<K>>,
+}
+
+impl<'a, T, K, F> Chunked<'a, T, K, F> {
+	pub(crate) fn new(slice: &'a [T], key: F) -> Self {
+		Self {
+			remaining: slice,
+			key,
+			pending: None,
+		}
+	}
+}
+
+impl<'a, T, K, F> Iterator for Chunked<'a, T, K, F>
+where
+	F: FnMut(&T) -> Chunk<K>,
+	K: PartialEq {
+	type Item = (K, &'a [T]);
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			let (first, len) = keyed_run(self.remaining, &mut self.pending, &mut self.key, |a, b| {
+				matches!((a, b), (Chunk::Key(a), Chunk::Key(b)) if a == b)
+			})?;
+			let (chunk, remaining) = self.remaining.split_at(len);
+			self.remaining = remaining;
+			match first {
+				Chunk::Key(chunk_key) | Chunk::Alone(chunk_key) => return Some((chunk_key, chunk)),
+				Chunk::Separator => {}
+			}
+		}
+	}
+}
+
+impl<'a, T, K, F> FusedIterator for Chunked<'a, T, K, F>
+where
+	F: FnMut(&T) -> Chunk<K>,
+	K: PartialEq {}
+
+#[cfg(test)]
+mod tests {
+	use super::Chunk;
+	use crate::ConsGroupExt;
+
+	#[test]
+	fn chunks_by_key() {
+		let slice = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+		let chunks: Vec<_> = slice.chunk(|n| Chunk::Key(n % 2 == 0)).collect();
+		assert_eq!(chunks, [
+			(false, &[3, 1][..]),
+			(true, &[4]),
+			(false, &[1, 5, 9]),
+			(true, &[2, 6]),
+			(false, &[5, 3, 5]),
+		]);
+	}
+
+	#[test]
+	fn drops_separators() {
+		let lines = ["a", "b", "", "", "c", ""];
+		let paragraphs: Vec<_> = lines
+			.chunk(|l| if l.is_empty() { Chunk::Separator } else { Chunk::Key(()) })
+			.map(|(_, p)| p)
+			.collect();
+		assert_eq!(paragraphs, [&lines[0..2], &lines[4..5]]);
+		assert_eq!([1, 2].chunk(|_| Chunk::<()>::Separator).count(), 0);
+	}
+
+	#[test]
+	fn keeps_alone_elements_apart() {
+		let lines = ["a", "# x", "# y", "b", "c"];
+		let chunks: Vec<_> = lines
+			.chunk(|l| if l.starts_with('#') { Chunk::Alone("comment") } else { Chunk::Key("code") })
+			.collect();
+		assert_eq!(chunks, [
+			("code", &lines[0..1]),
+			("comment", &lines[1..2]),
+			("comment", &lines[2..3]),
+			("code", &lines[3..5]),
+		]);
+	}
+
+	#[test]
+	fn computes_each_key_once() {
+		let slice = [1, 1, 0, 2, 3, 3, 4];
+		let mut calls = 0;
+		let chunks = slice.chunk(|&n| {
+			calls += 1;
+			match n {
+				0 => Chunk::Separator,
+				4 => Chunk::Alone(n),
+				_ => Chunk::Key(n),
+			}
+		});
+		assert_eq!(chunks.count(), 4);
+		assert_eq!(calls, slice.len());
+	}
+}
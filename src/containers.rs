@@ -0,0 +1,107 @@
+//! [`ConsGroupExt`] for the usual owners of a slice, so that generic code
+//! bounded on `ConsGroupExt<T>` accepts them without reborrowing.
+//!
+//! Method calls already reach the `[T]` implementation through auto-deref,
+//! these only lend the slice they own.
+
+use crate::ConsGroupExt;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+
+impl<T, const LEN: usize> ConsGroupExt<T> for [T; LEN] {
+	fn as_group_slice(&self) -> &[T] {
+		self
+	}
+
+	fn as_group_slice_mut(&mut self) -> &mut [T] {
+		self
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ConsGroupExt<T> for Vec<T> {
+	fn as_group_slice(&self) -> &[T] {
+		self
+	}
+
+	fn as_group_slice_mut(&mut self) -> &mut [T] {
+		self
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ConsGroupExt<T> for Box<[T]> {
+	fn as_group_slice(&self) -> &[T] {
+		self
+	}
+
+	fn as_group_slice_mut(&mut self) -> &mut [T] {
+		self
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Clone> ConsGroupExt<T> for Cow<'a, [T]> {
+	fn as_group_slice(&self) -> &[T] {
+		self
+	}
+
+	// `Cow` has no `DerefMut`: mutating a borrowed slice clones it first.
+	fn as_group_slice_mut(&mut self) -> &mut [T] {
+		self.to_mut()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::ConsGroupExt;
+
+	fn run_lens<S>(runs: &S) -> [usize; 3]
+	where S: ConsGroupExt<i32> + ?Sized {
+		let mut lens = [0; 3];
+		for (len, run) in lens.iter_mut().zip(runs.cons_group()) {
+			*len = run.len();
+		}
+		lens
+	}
+
+	#[test]
+	fn groups_arrays() {
+		let mut array = [1, 1, 2, 3, 3, 3];
+		assert_eq!(run_lens(&array), [2, 1, 3]);
+		for run in ConsGroupExt::cons_group_mut(&mut array) {
+			run[0] = 0;
+		}
+		assert_eq!(array, [0, 1, 0, 0, 3, 3]);
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn groups_owned_slices() {
+		use alloc::borrow::Cow;
+
+		let mut vec = vec![1, 1, 2, 3, 3, 3];
+		assert_eq!(run_lens(&vec), [2, 1, 3]);
+		assert_eq!(ConsGroupExt::cons_group_mut(&mut vec).count(), 3);
+		let boxed = vec.clone().into_boxed_slice();
+		assert_eq!(run_lens(&boxed), [2, 1, 3]);
+		let owned: Cow<[i32]> = Cow::Owned(vec.clone());
+		assert_eq!(run_lens(&owned), [2, 1, 3]);
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn clones_borrowed_cow_on_mutation() {
+		use alloc::borrow::Cow;
+
+		let slice = [1, 1, 2];
+		let mut cow = Cow::Borrowed(&slice[..]);
+		assert_eq!(run_lens(&cow), [2, 1, 0]);
+		for run in cow.cons_group_mut() {
+			run[0] = 0;
+		}
+		assert!(matches!(cow, Cow::Owned(_)));
+		assert_eq!(*cow, [0, 1, 0]);
+		assert_eq!(slice, [1, 1, 2]);
+	}
+}
@@ -0,0 +1,107 @@
+//! Deduplication of consecutive elements that keeps track of run lengths.
+
+use crate::ConsGroup;
+#[cfg(feature = "alloc")]
+use crate::ConsGroupExt;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Removes consecutive repeated elements like [`Vec::dedup`], returning how
+/// many times each remaining element was repeated.
+///
+/// # Example
+///
+/// ```
+/// use each_cons::dedup_runs_in_place;
+///
+/// let mut log = vec!["connected", "timeout", "timeout", "timeout", "closed"];
+/// let counts = dedup_runs_in_place(&mut log);
+/// assert_eq!(log, ["connected", "timeout", "closed"]);
+/// assert_eq!(counts, [1, 3, 1]);
+/// for (message, count) in log.iter().zip(counts) {
+///     if count > 1 {
+///         println!("{} (x{})", message, count);
+///     } else {
+///         println!("{}", message);
+///     }
+/// }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn dedup_runs_in_place<T>(vec: &mut Vec<T>) -> Vec<usize>
+where T: Eq {
+	let counts = vec.cons_group().map(<[T]>::len).collect();
+	vec.dedup();
+	counts
+}
+
+/// Iterator over the first element of each run of identical elements, see
+/// [`ConsGroupExt::unique_consecutive`].
+///
+/// [`ConsGroupExt::unique_consecutive`]: crate::ConsGroupExt::unique_consecutive
+pub struct UniqueConsecutive<'a, T> {
+	runs: ConsGroup<'a, T>,
+}
+
+impl<'a, T> UniqueConsecutive<'a, T>
+where T: Eq {
+	pub(crate) fn new(slice: &'a [T]) -> Self {
+		Self {
+			runs: ConsGroup::new(slice),
+		}
+	}
+}
+
+impl<'a, T> Iterator for UniqueConsecutive<'a, T>
+where T: Eq {
+	type Item = &'a T;
+	fn next(&mut self) -> Option<Self::Item> {
+		self.runs.next().map(|run| &run[0])
+	}
+}
+
+impl<'a, T> DoubleEndedIterator for UniqueConsecutive<'a, T>
+where T: Eq {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.runs.next_back().map(|run| &run[0])
+	}
+}
+
+impl<'a, T> FusedIterator for UniqueConsecutive<'a, T>
+where T: Eq {}
+
+#[cfg(test)]
+mod tests {
+	#[cfg(feature = "alloc")]
+	use super::dedup_runs_in_place;
+	use crate::ConsGroupExt;
+
+	#[test]
+	fn yields_first_element_of_each_run() {
+		let slice = [1, 1, 2, 3, 3, 3, 4, 5, 5];
+		let unique: Vec<_> = slice.unique_consecutive().copied().collect();
+		assert_eq!(unique, [1, 2, 3, 4, 5]);
+		let unique: Vec<_> = slice.unique_consecutive().rev().copied().collect();
+		assert_eq!(unique, [5, 4, 3, 2, 1]);
+		assert!([0; 0].unique_consecutive().next().is_none());
+	}
+
+	#[test]
+	fn borrows_from_the_slice() {
+		let slice = [(1, 'a'), (1, 'a'), (2, 'b')];
+		let first = slice.unique_consecutive().next().unwrap();
+		assert!(core::ptr::eq(first, &slice[0]));
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn counts_removed_duplicates() {
+		let mut vec = vec![1, 1, 2, 3, 3, 3, 1, 5, 5];
+		let expected: Vec<_> = vec.unique_consecutive().copied().collect();
+		let counts = dedup_runs_in_place(&mut vec);
+		assert_eq!(vec, expected);
+		assert_eq!(counts, [2, 1, 3, 1, 2]);
+		let mut empty: Vec<u8> = Vec::new();
+		assert!(dedup_runs_in_place(&mut empty).is_empty());
+	}
+}
@@ -0,0 +1,164 @@
+//! Run grouping over ring buffers, whose contents may wrap around.
+
+use crate::{run_len, run_len_back};
+use alloc::collections::VecDeque;
+use core::iter::FusedIterator;
+use core::mem;
+
+/// Add this into scope to give your `VecDeque`s the `cons_group()` method.
+///
+/// A `VecDeque` may hold its elements in two slices, see
+/// [`VecDeque::as_slices`]. Runs are yielded the same way, as a pair of
+/// slices, the second one being empty unless the run wraps around the end
+/// of the buffer. This avoids calling [`VecDeque::make_contiguous`], which
+/// needs mutable access and may move every element.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::VecDeque;
+/// use each_cons::DequeConsGroupExt;
+///
+/// let mut samples: VecDeque<u8> = VecDeque::new();
+/// samples.extend([7, 7, 0]);
+/// samples.push_front(7);
+/// let lens: Vec<usize> = samples
+///     .cons_group()
+///     .map(|(front, back)| front.len() + back.len())
+///     .collect();
+/// assert_eq!(lens, [3, 1]);
+/// ```
+pub trait DequeConsGroupExt<T> {
+	fn cons_group(&self) -> DequeConsGroup<'_, T>
+	where T: Eq;
+}
+
+impl<T> DequeConsGroupExt<T> for VecDeque<T> {
+	fn cons_group(&self) -> DequeConsGroup<'_, T>
+	where T: Eq {
+		let (front, back) = self.as_slices();
+		DequeConsGroup {
+			front,
+			back,
+		}
+	}
+}
+
+/// Iterator over runs of identical elements of a `VecDeque`, see
+/// [`DequeConsGroupExt::cons_group`].
+pub struct DequeConsGroup<'a, T> {
+	front: &'a [T],
+	back: &'a [T],
+}
+
+impl<'a, T> Iterator for DequeConsGroup<'a, T>
+where T: Eq {
+	type Item = (&'a [T], &'a [T]);
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.front.is_empty() {
+			self.front = mem::take(&mut self.back);
+		}
+		let first = self.front.first()?;
+		let len = run_len(self.front, |a, b| a == b);
+		if len < self.front.len() {
+			let (run, front) = self.front.split_at(len);
+			self.front = front;
+			return Some((run, &[]));
+		}
+		let wrapped = match self.back.first() {
+			Some(next) if next == first => run_len(self.back, |a, b| a == b),
+			_ => 0,
+		};
+		let run = mem::take(&mut self.front);
+		let (wrapped, back) = self.back.split_at(wrapped);
+		self.front = back;
+		self.back = &[];
+		Some((run, wrapped))
+	}
+}
+
+impl<'a, T> DoubleEndedIterator for DequeConsGroup<'a, T>
+where T: Eq {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.back.is_empty() {
+			self.back = mem::take(&mut self.front);
+		}
+		let last = self.back.last()?;
+		let len = run_len_back(self.back, |a, b| a == b);
+		if len < self.back.len() {
+			let (back, run) = self.back.split_at(self.back.len() - len);
+			self.back = back;
+			return Some((run, &[]));
+		}
+		let wrapped = match self.front.last() {
+			Some(previous) if previous == last => run_len_back(self.front, |a, b| a == b),
+			_ => 0,
+		};
+		let run = mem::take(&mut self.back);
+		let (front, wrapped) = self.front.split_at(self.front.len() - wrapped);
+		self.back = front;
+		self.front = &[];
+		if wrapped.is_empty() { Some((run, wrapped)) } else { Some((wrapped, run)) }
+	}
+}
+
+impl<'a, T> FusedIterator for DequeConsGroup<'a, T>
+where T: Eq {}
+
+#[cfg(test)]
+mod tests {
+	use super::DequeConsGroupExt;
+	use crate::ConsGroupExt;
+	use alloc::collections::VecDeque;
+
+	/// Deque holding `slice` with its first `split` elements pushed at the
+	/// front, which wraps them around the end of the buffer.
+	fn wrapped(slice: &[i32], split: usize) -> VecDeque<i32> {
+		let mut deque = VecDeque::new();
+		deque.extend(&slice[split..]);
+		for &x in slice[..split].iter().rev() {
+			deque.push_front(x);
+		}
+		deque
+	}
+
+	fn concat((front, back): (&[i32], &[i32])) -> Vec<i32> {
+		[front, back].concat()
+	}
+
+	#[test]
+	fn merges_runs_across_the_wrap_around() {
+		let deque = wrapped(&[1, 2, 2, 2, 3], 2);
+		assert_eq!(deque.as_slices(), (&[1, 2][..], &[2, 2, 3][..]));
+		let runs: Vec<_> = deque.cons_group().collect();
+		assert_eq!(runs, [(&[1][..], &[][..]), (&[2], &[2, 2]), (&[3], &[])]);
+		let runs: Vec<_> = deque.cons_group().rev().collect();
+		assert_eq!(runs, [(&[3][..], &[][..]), (&[2], &[2, 2]), (&[1], &[])]);
+		assert!(VecDeque::<i32>::new().cons_group().next().is_none());
+	}
+
+	#[test]
+	fn matches_contiguous_grouping() {
+		let slice = [0, 0, 1, 1, 1, 0, 2, 2, 0, 0];
+		let expected: Vec<_> = slice.cons_group().map(<[i32]>::to_vec).collect();
+		for split in 0..=slice.len() {
+			let deque = wrapped(&slice, split);
+			let runs: Vec<_> = deque.cons_group().map(concat).collect();
+			assert_eq!(runs, expected, "split at {}", split);
+			let mut runs: Vec<_> = deque.cons_group().rev().map(concat).collect();
+			runs.reverse();
+			assert_eq!(runs, expected, "split at {}", split);
+		}
+	}
+
+	#[test]
+	fn meets_in_the_middle() {
+		let deque = wrapped(&[1, 1, 2, 2, 3, 3], 3);
+		let mut runs = deque.cons_group().map(concat);
+		assert_eq!(runs.next_back(), Some(vec![3, 3]));
+		assert_eq!(runs.next(), Some(vec![1, 1]));
+		assert_eq!(runs.next_back(), Some(vec![2, 2]));
+		assert_eq!(runs.next(), None);
+		assert_eq!(runs.next_back(), None);
+	}
+}
@@ -0,0 +1,157 @@
+//! Port of ruby's `Enumerable#each_slice` for iterators.
+
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// What to do with a final batch holding fewer than `size` items.
+enum Partial<T> {
+	Keep,
+	Drop,
+	/// Fill with clones of the value, the function being `T::clone` so that
+	/// only [`EachSlice::pad`] requires `T: Clone`.
+	Pad(T, fn(&T) -> T),
+}
+
+/// Iterator over batches of consecutive items, see
+/// [`ConsIterator::each_slice`].
+///
+/// By default the final batch may hold fewer than `size` items, as with
+/// ruby. Use [`EachSlice::drop_partial`] or [`EachSlice::pad`] to change
+/// that.
+///
+/// [`ConsIterator::each_slice`]: crate::ConsIterator::each_slice
+pub struct EachSlice<I: Iterator> {
+	iter: I,
+	size: usize,
+	partial: Partial<I::Item>,
+	done: bool,
+}
+
+impl<I: Iterator> EachSlice<I> {
+	pub(crate) fn new(iter: I, size: usize) -> Self {
+		assert!(size != 0, "size must be non-zero");
+		Self {
+			iter,
+			size,
+			partial: Partial::Keep,
+			done: false,
+		}
+	}
+
+	/// Skips the final batch if it holds fewer than `size` items.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsIterator;
+	///
+	/// let batches: Vec<Vec<i32>> = (1..=5).each_slice(2).drop_partial().collect();
+	/// assert_eq!(batches, [[1, 2], [3, 4]]);
+	/// ```
+	pub fn drop_partial(mut self) -> Self {
+		self.partial = Partial::Drop;
+		self
+	}
+
+	/// Fills the final batch with clones of `fill` up to `size` items.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsIterator;
+	///
+	/// let batches: Vec<Vec<i32>> = (1..=5).each_slice(2).pad(0).collect();
+	/// assert_eq!(batches, [[1, 2], [3, 4], [5, 0]]);
+	/// ```
+	pub fn pad(mut self, fill: I::Item) -> Self
+	where I::Item: Clone {
+		self.partial = Partial::Pad(fill, I::Item::clone);
+		self
+	}
+}
+
+impl<I: Iterator> Iterator for EachSlice<I> {
+	type Item = Vec<I::Item>;
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done { return None; }
+		let mut batch = Vec::with_capacity(self.size);
+		batch.extend(self.iter.by_ref().take(self.size));
+		if batch.len() == self.size { return Some(batch); }
+		self.done = true;
+		if batch.is_empty() { return None; }
+		match &self.partial {
+			Partial::Keep => Some(batch),
+			Partial::Drop => None,
+			Partial::Pad(fill, clone) => {
+				batch.resize_with(self.size, || clone(fill));
+				Some(batch)
+			}
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		if self.done { return (0, Some(0)); }
+		let batches = |len: usize| match self.partial {
+			Partial::Drop => len / self.size,
+			_ => len.div_ceil(self.size),
+		};
+		let (lower, upper) = self.iter.size_hint();
+		(batches(lower), upper.map(batches))
+	}
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for EachSlice<I> {}
+
+impl<I: Iterator> FusedIterator for EachSlice<I> {}
+
+#[cfg(test)]
+mod tests {
+	use crate::ConsIterator;
+
+	#[test]
+	fn yields_batches() {
+		let mut batches = (1..=7).each_slice(3);
+		assert_eq!(batches.next(), Some(vec![1, 2, 3]));
+		assert_eq!(batches.next(), Some(vec![4, 5, 6]));
+		assert_eq!(batches.next(), Some(vec![7]));
+		assert_eq!(batches.next(), None);
+		assert_eq!((1..=6).each_slice(3).count(), 2);
+		assert_eq!((0..0).each_slice(3).count(), 0);
+	}
+
+	#[test]
+	fn handles_partial_batches() {
+		let kept: Vec<_> = (1..=7).each_slice(3).collect();
+		assert_eq!(kept.last(), Some(&vec![7]));
+		let dropped: Vec<_> = (1..=7).each_slice(3).drop_partial().collect();
+		assert_eq!(dropped, [[1, 2, 3], [4, 5, 6]]);
+		let padded: Vec<_> = (1..=7).each_slice(3).pad(0).collect();
+		assert_eq!(padded, [[1, 2, 3], [4, 5, 6], [7, 0, 0]]);
+		let padded: Vec<_> = (1..=6).each_slice(3).pad(0).collect();
+		assert_eq!(padded, [[1, 2, 3], [4, 5, 6]]);
+	}
+
+	#[test]
+	fn batches_items_without_clone() {
+		struct Row(u32);
+		let rows = (0..5).map(Row).each_slice(2).drop_partial();
+		let sums: Vec<u32> = rows.map(|batch| batch.iter().map(|row| row.0).sum()).collect();
+		assert_eq!(sums, [1, 5]);
+	}
+
+	#[test]
+	fn reports_exact_size() {
+		assert_eq!((0..7).each_slice(3).len(), 3);
+		assert_eq!((0..7).each_slice(3).pad(0).len(), 3);
+		assert_eq!((0..7).each_slice(3).drop_partial().len(), 2);
+		let mut batches = (0..7).each_slice(3);
+		batches.next();
+		assert_eq!(batches.len(), 2);
+	}
+
+	#[test]
+	#[should_panic(expected = "size must be non-zero")]
+	fn rejects_zero_size() {
+		(0..10).each_slice(0);
+	}
+}
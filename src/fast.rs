@@ -0,0 +1,144 @@
+//! Chunked run boundary detection for primitive element types.
+//!
+//! Instead of comparing elements one at a time, [`ConsGroupFast`] compares
+//! fixed-size chunks against the first element of the run with a branchless
+//! fold. Only the chunk holding the boundary is then scanned element by
+//! element.
+//!
+//! Runs of up to `CHUNK` elements never reach the chunked comparison, which
+//! is kept out of line so that the scan of short runs is inlined into the
+//! iterator.
+
+use core::convert::TryFrom;
+
+/// Number of elements compared at once.
+const CHUNK: usize = 32;
+
+mod sealed {
+	pub trait Sealed {}
+}
+
+/// Primitive types supported by [`ConsGroupExt::cons_group_fast`].
+///
+/// Elements are compared with `==`: for floats this means `NaN` is never
+/// part of a longer run and `0.0` and `-0.0` belong to the same run, just as
+/// with `slice.chunk_while(|a, b| a == b)`.
+///
+/// [`ConsGroupExt::cons_group_fast`]: crate::ConsGroupExt::cons_group_fast
+pub trait FastGroup: Copy + PartialEq + sealed::Sealed {}
+
+macro_rules! fast_group {
+	($($t:ty)*) => {$(
+		impl sealed::Sealed for $t {}
+		impl FastGroup for $t {}
+	)*};
+}
+
+fast_group!(u8 i8 u16 i16 u32 i32 u64 i64 u128 i128 usize isize f32 f64);
+
+/// Length of the run at the start of a non-empty `slice`.
+///
+/// Short runs are common and would pay for a whole chunk comparison, so the
+/// first `CHUNK` elements are scanned one at a time.
+#[inline]
+fn run_len<T: FastGroup>(slice: &[T]) -> usize {
+	let first = slice[0];
+	let end = slice.len().min(CHUNK + 1);
+	match slice[1..end].iter().position(|&x| x != first) {
+		Some(i) => 1 + i,
+		None => end + long_run_len(&slice[end..], first),
+	}
+}
+
+/// Length of the run of `first` at the start of `slice`, comparing whole
+/// chunks at once. Kept out of line so that [`run_len`] stays small enough
+/// to be inlined for short runs.
+#[inline(never)]
+fn long_run_len<T: FastGroup>(slice: &[T], first: T) -> usize {
+	let mut len = 0;
+	let mut chunks = slice.chunks_exact(CHUNK);
+	for chunk in &mut chunks {
+		let chunk = <&[T; CHUNK]>::try_from(chunk).unwrap();
+		if !chunk.iter().fold(true, |same, &x| same & (x == first)) {
+			return len + chunk.iter().position(|&x| x != first).unwrap();
+		}
+		len += CHUNK;
+	}
+	let remainder = chunks.remainder();
+	len + remainder.iter().position(|&x| x != first).unwrap_or(remainder.len())
+}
+
+/// Iterator over runs of identical primitive elements, see
+/// [`ConsGroupExt::cons_group_fast`].
+///
+/// [`ConsGroupExt::cons_group_fast`]: crate::ConsGroupExt::cons_group_fast
+pub struct ConsGroupFast<'a, T> {
+	remaining: &'a [T],
+}
+
+impl<'a, T> ConsGroupFast<'a, T>
+where T: FastGroup {
+	pub(crate) fn new(slice: &'a [T]) -> Self {
+		Self {
+			remaining: slice
+		}
+	}
+}
+
+impl<'a, T> Iterator for ConsGroupFast<'a, T>
+where T: FastGroup {
+	type Item = &'a [T];
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.remaining.is_empty() { return None; }
+		let (run, remaining) = self.remaining.split_at(run_len(self.remaining));
+		self.remaining = remaining;
+		Some(run)
+	}
+}
+
+impl<'a, T> core::iter::FusedIterator for ConsGroupFast<'a, T>
+where T: FastGroup {}
+
+#[cfg(test)]
+mod tests {
+	use crate::ConsGroupExt;
+
+	fn assert_same_runs<T: super::FastGroup + Eq + std::fmt::Debug>(slice: &[T]) {
+		let fast: Vec<_> = slice.cons_group_fast().collect();
+		let scalar: Vec<_> = slice.cons_group().collect();
+		assert_eq!(fast, scalar);
+	}
+
+	#[test]
+	fn matches_scalar_grouping() {
+		assert_same_runs::<u8>(&[]);
+		assert_same_runs(&[1u8]);
+		assert_same_runs(&[1i32, 1, 2, 3, 3, 3, 4, 5, 5]);
+		for len in [31, 32, 33, 64, 65, 97, 130].iter() {
+			let mut slice = vec![9u8; *len];
+			assert_same_runs(&slice);
+			for i in 0..*len {
+				slice[i] = 0;
+				assert_same_runs(&slice);
+				slice[i] = 9;
+			}
+		}
+	}
+
+	#[test]
+	fn matches_scalar_grouping_on_mixed_runs() {
+		let slice: Vec<u16> = (0..10_000u16).map(|i| ((i / 37) ^ (i / 101)) % 3).collect();
+		assert_same_runs(&slice);
+		let slice: Vec<i64> = (0..10_000i64).map(|i| i / 40).collect();
+		assert_same_runs(&slice);
+	}
+
+	#[test]
+	fn compares_floats_with_partial_eq() {
+		let slice = [1.5f32, 1.5, f32::NAN, f32::NAN, 0.0, -0.0, 2.0];
+		let fast: Vec<_> = slice.cons_group_fast().collect();
+		let scalar: Vec<_> = slice.chunk_while(|a, b| a == b).collect();
+		assert_eq!(format!("{:?}", fast), format!("{:?}", scalar));
+		assert_eq!(fast.iter().map(|run| run.len()).collect::<Vec<_>>(), [2, 1, 1, 2, 1]);
+	}
+}
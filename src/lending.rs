@@ -0,0 +1,129 @@
+//! Run grouping over iterators that lends each run instead of collecting it.
+
+use core::iter::{Fuse, FusedIterator};
+
+/// Runs of identical consecutive items, each lent as an iterator over the
+/// source, see [`ConsIterator::cons_group_lending`].
+///
+/// This is not an [`Iterator`]: a [`LendingRun`] borrows this value, so
+/// runs are taken one at a time with [`ConsGroupLending::next_run`]. In
+/// exchange runs are never buffered, and items a run did not yield are
+/// skipped when taking the next one.
+///
+/// [`ConsIterator::cons_group_lending`]: crate::ConsIterator::cons_group_lending
+pub struct ConsGroupLending<I: Iterator> {
+	iter: Fuse<I>,
+	/// Item read ahead from `iter`, to know whether the run goes on.
+	pending: Option<I::Item>,
+	/// Whether `pending` is part of the current run.
+	continues: bool,
+}
+
+impl<I: Iterator> ConsGroupLending<I>
+where I::Item: Eq {
+	pub(crate) fn new(iter: I) -> Self {
+		Self {
+			iter: iter.fuse(),
+			pending: None,
+			continues: false,
+		}
+	}
+
+	/// Lends the next run, skipping what is left of the previous one.
+	pub fn next_run(&mut self) -> Option<LendingRun<'_, I>> {
+		while self.continues {
+			self.advance();
+		}
+		if self.pending.is_none() {
+			self.pending = Some(self.iter.next()?);
+		}
+		self.continues = true;
+		Some(LendingRun {
+			runs: self,
+		})
+	}
+
+	/// Yields the pending item, reading the one after it.
+	fn advance(&mut self) -> Option<I::Item> {
+		let item = self.pending.take()?;
+		self.pending = self.iter.next();
+		self.continues = self.pending.as_ref().is_some_and(|next| *next == item);
+		Some(item)
+	}
+}
+
+/// Iterator over the items of a single run, see
+/// [`ConsGroupLending::next_run`].
+pub struct LendingRun<'a, I: Iterator> {
+	runs: &'a mut ConsGroupLending<I>,
+}
+
+impl<'a, I: Iterator> Iterator for LendingRun<'a, I>
+where I::Item: Eq {
+	type Item = I::Item;
+	fn next(&mut self) -> Option<Self::Item> {
+		if !self.runs.continues { return None; }
+		self.runs.advance()
+	}
+}
+
+impl<'a, I: Iterator> FusedIterator for LendingRun<'a, I>
+where I::Item: Eq {}
+
+#[cfg(test)]
+mod tests {
+	use crate::ConsIterator;
+
+	#[test]
+	fn lends_each_run() {
+		let mut runs = [1, 1, 2, 3, 3, 3, 1].iter().cons_group_lending();
+		let mut sums = [0; 5];
+		let mut count = 0;
+		while let Some(run) = runs.next_run() {
+			sums[count] = run.sum();
+			count += 1;
+		}
+		assert_eq!(sums[..count], [2, 2, 9, 1]);
+		assert!(runs.next_run().is_none());
+		assert!(core::iter::empty::<u8>().cons_group_lending().next_run().is_none());
+	}
+
+	#[test]
+	fn skips_unconsumed_items() {
+		let mut runs = "aaabccdd".chars().cons_group_lending();
+		assert_eq!(runs.next_run().unwrap().next(), Some('a'));
+		assert_eq!(runs.next_run().unwrap().count(), 1);
+		runs.next_run();
+		let mut last = runs.next_run().unwrap();
+		assert_eq!((last.next(), last.next(), last.next()), (Some('d'), Some('d'), None));
+		assert!(runs.next_run().is_none());
+	}
+
+	#[test]
+	fn moves_items_out_of_the_source() {
+		struct Token(u8);
+		impl PartialEq for Token {
+			fn eq(&self, other: &Self) -> bool {
+				self.0 == other.0
+			}
+		}
+		impl Eq for Token {}
+		let mut runs = [0, 0, 1].iter().map(|&n| Token(n)).cons_group_lending();
+		let first: Option<Token> = runs.next_run().and_then(|mut run| run.nth(1));
+		assert_eq!(first.map(|t| t.0), Some(0));
+		assert_eq!(runs.next_run().map(Iterator::count), Some(1));
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn matches_owned_runs() {
+		let items = [0, 0, 1, 0, 2, 2, 2, 0, 0];
+		let expected: Vec<Vec<i32>> = items.iter().copied().cons_group().collect();
+		let mut runs = items.iter().copied().cons_group_lending();
+		let mut lent = Vec::new();
+		while let Some(run) = runs.next_run() {
+			lent.push(run.collect::<Vec<_>>());
+		}
+		assert_eq!(lent, expected);
+	}
+}
@@ -1,3 +1,4 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![doc(html_playground_url = "https://play.rust-lang.org/")]
 #![doc(issue_tracker_base_url = "https://github.com/BuonOmo/each_cons.rs/issues/")]
 
@@ -8,23 +9,723 @@
 //! 1. `iter.each_cons(N)` (See [`ConsIterator`])
 //! 2. `each_cons(N, iter)` (See [`each_cons`])
 //!
-//! Both will have the same behaviour: returning a `Cons` struct that is
+//! Both will have the same behaviour: returning a [`Cons`] struct that is
 //! an [`Iterator`] of `Vec<Rc<Item>>`, where `Vec` size is the given `N`
 //! and `Item` correspond to the item of the previous iterator given.
+//!
+//! # Features
+//!
+//! The crate is `no_std`: grouping slices with [`ConsGroupExt`] only needs
+//! `core`. Adapters that allocate, such as [`Cons`], [`ConsGroupIter`] or
+//! [`Rle`], need the `alloc` feature, which the default `std` feature
+//! enables. Strings are grouped by `char` with [`StrConsGroupExt`], or by
+//! grapheme cluster with the `unicode-segmentation` feature.
+//!
+#![cfg_attr(not(feature = "alloc"), doc = "
+[`each_cons`]: #features
+[`Cons`]: #features
+[`ConsGroupIter`]: #features
+[`Rle`]: #features
+")]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod array;
+mod chunk;
+mod containers;
+mod dedup;
+#[cfg(feature = "alloc")]
+mod deque;
+#[cfg(feature = "alloc")]
+mod each_slice;
+mod fast;
+mod lending;
+#[cfg(feature = "rayon")]
+mod par;
+mod slice_by;
+pub mod rle;
+#[cfg(feature = "alloc")]
+mod stats;
+#[cfg(feature = "futures")]
+mod stream;
+mod text;
+
+#[cfg(feature = "alloc")]
+pub use rle::Rle;
+pub use array::{ConsArray, ConsArrayIter};
+pub use chunk::{Chunk, Chunked};
+#[cfg(feature = "alloc")]
+pub use dedup::dedup_runs_in_place;
+pub use dedup::UniqueConsecutive;
+#[cfg(feature = "alloc")]
+pub use deque::{DequeConsGroup, DequeConsGroupExt};
+#[cfg(feature = "alloc")]
+pub use each_slice::EachSlice;
+pub use fast::{ConsGroupFast, FastGroup};
+pub use lending::{ConsGroupLending, LendingRun};
+#[cfg(feature = "rayon")]
+pub use par::{ParConsGroup, ParConsGroupExt};
+pub use rle::{rle_encode, RleEncode};
+pub use slice_by::{SliceAfter, SliceBefore};
+#[cfg(feature = "alloc")]
+pub use slice_by::{SliceAfterIter, SliceBeforeIter};
+#[cfg(feature = "alloc")]
+pub use stats::RunStats;
+#[cfg(feature = "futures")]
+pub use stream::{ConsGroupStream, ConsStream, ConsStreamExt};
+#[cfg(feature = "unicode-segmentation")]
+pub use text::GraphemeConsGroup;
+pub use text::{StrConsGroup, StrConsGroupExt};
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::VecDeque, rc::Rc, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::iter::FusedIterator;
+use core::ops::Range;
 
 /// Add this into scope to give your iterators the `each_cons(N)` method.
+#[cfg_attr(feature = "alloc", doc = r#"
+# Example
+
+```
+use each_cons::ConsIterator;
+
+let v = vec!["foo", "bar", "baz"];
+let mut cons = v.iter().each_cons(2);
+assert_eq!(cons.next().unwrap().iter().map(|s| ***s).collect::<Vec<_>>(), ["foo", "bar"]);
+assert_eq!(cons.next().unwrap().iter().map(|s| ***s).collect::<Vec<_>>(), ["bar", "baz"]);
+assert!(cons.next().is_none());
+```
+"#)]
+pub trait ConsIterator: Iterator + Sized {
+	/// Returns an iterator over every window of `size` consecutive items.
+	///
+	/// # Panics
+	///
+	/// Panics if `size` is 0.
+	#[cfg(feature = "alloc")]
+	fn each_cons(self, size: usize) -> Cons<Self> {
+		Cons::new(self, size)
+	}
+
+	/// Returns an iterator over every window of `N` consecutive items as an
+	/// array, cloning each item into the `N` windows it is part of.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsIterator;
+	///
+	/// let words = "the quick brown fox".split(' ');
+	/// for [a, b] in words.each_cons_array() {
+	///     println!("{} -> {}", a, b);
+	/// }
+	/// let sums: Vec<i32> = (1..=5).each_cons_array().map(|[a, b, c]| a + b + c).collect();
+	/// assert_eq!(sums, [6, 9, 12]);
+	/// ```
+	///
+	/// # Panics
+	///
+	/// Panics if `N` is 0.
+	fn each_cons_array<const N: usize>(self) -> ConsArrayIter<Self, N>
+	where Self::Item: Clone {
+		ConsArrayIter::new(self)
+	}
+
+	/// Port of ruby's [`Enumerable#each_slice`](https://rubydoc.info/stdlib/core/Enumerable:each_slice):
+	/// returns an iterator over batches of `size` consecutive items, the
+	/// last one possibly being shorter.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsIterator;
+	///
+	/// let lines = "a\nb\nc\nd\ne".lines();
+	/// let batches: Vec<Vec<&str>> = lines.each_slice(2).collect();
+	/// assert_eq!(batches, [vec!["a", "b"], vec!["c", "d"], vec!["e"]]);
+	/// ```
+	///
+	/// # Panics
+	///
+	/// Panics if `size` is 0.
+	#[cfg(feature = "alloc")]
+	fn each_slice(self, size: usize) -> EachSlice<Self> {
+		EachSlice::new(self, size)
+	}
+
+	/// Starts a new group at each item for which `predicate` holds, the
+	/// iterator counterpart of [`ConsGroupExt::slice_before`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsIterator;
+	///
+	/// let log = "BEGIN\na\nBEGIN\nb\nc".lines();
+	/// let records: Vec<Vec<&str>> = log.slice_before(|l| *l == "BEGIN").collect();
+	/// assert_eq!(records, [vec!["BEGIN", "a"], vec!["BEGIN", "b", "c"]]);
+	/// ```
+	#[cfg(feature = "alloc")]
+	fn slice_before<P>(self, predicate: P) -> SliceBeforeIter<Self, P>
+	where P: FnMut(&Self::Item) -> bool {
+		SliceBeforeIter::new(self, predicate)
+	}
+
+	/// Ends a group at each item for which `predicate` holds, the iterator
+	/// counterpart of [`ConsGroupExt::slice_after`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsIterator;
+	///
+	/// let log = "a\nEND\nb\nc\nEND".lines();
+	/// let records: Vec<Vec<&str>> = log.slice_after(|l| *l == "END").collect();
+	/// assert_eq!(records, [vec!["a", "END"], vec!["b", "c", "END"]]);
+	/// ```
+	#[cfg(feature = "alloc")]
+	fn slice_after<P>(self, predicate: P) -> SliceAfterIter<Self, P>
+	where P: FnMut(&Self::Item) -> bool {
+		SliceAfterIter::new(self, predicate)
+	}
+
+	/// Groups consecutive identical items into owned runs, the iterator
+	/// counterpart of [`ConsGroupExt::cons_group`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsIterator;
+	///
+	/// let lines = "a\na\nb\na".lines();
+	/// let runs: Vec<Vec<&str>> = lines.cons_group().collect();
+	/// assert_eq!(runs, [vec!["a", "a"], vec!["b"], vec!["a"]]);
+	/// ```
+	#[cfg(feature = "alloc")]
+	fn cons_group(self) -> ConsGroupIter<Self>
+	where Self::Item: Eq {
+		ConsGroupIter::new(self)
+	}
+
+	/// Groups consecutive identical items like [`ConsGroupExt::cons_group`],
+	/// lending each run as an iterator over `self` rather than collecting
+	/// it, so that summing or counting runs does not allocate.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsIterator;
+	///
+	/// let mut runs = [4, 4, 4, 1, 4, 4].iter().cons_group_lending();
+	/// let mut longest = 0;
+	/// while let Some(run) = runs.next_run() {
+	///     longest = longest.max(run.count());
+	/// }
+	/// assert_eq!(longest, 3);
+	/// ```
+	fn cons_group_lending(self) -> ConsGroupLending<Self>
+	where Self::Item: Eq {
+		ConsGroupLending::new(self)
+	}
+}
+
+impl<I: Iterator> ConsIterator for I {}
+
+/// If you don't like `iter.each_cons(N)`, use this.
 ///
 /// # Example
 ///
-pub trait ConsGroupExt<T>
-where T: Eq {
-	fn cons_group(&self) -> ConsGroup<'_, T> ;
+/// ```
+/// use each_cons::each_cons;
+///
+/// let windows: Vec<Vec<i32>> = each_cons(3, 1..=5)
+///     .map(|cons| cons.iter().map(|n| **n).collect())
+///     .collect();
+/// assert_eq!(windows, [[1, 2, 3], [2, 3, 4], [3, 4, 5]]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `size` is 0.
+#[cfg(feature = "alloc")]
+pub fn each_cons<I>(size: usize, iter: I) -> Cons<I::IntoIter>
+where I: IntoIterator {
+	Cons::new(iter.into_iter(), size)
 }
 
-/// If you don't like `iter.each_cons(N)`, use this.
+/// Iterator over windows of consecutive items, see [`ConsIterator`] and
+/// [`each_cons`].
+///
+/// Items are wrapped in an [`Rc`] so that consecutive windows share them
+/// instead of requiring `Item: Clone`.
+#[cfg(feature = "alloc")]
+pub struct Cons<I: Iterator> {
+	iter: I,
+	size: usize,
+	window: VecDeque<Rc<I::Item>>,
+	done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator> Cons<I> {
+	fn new(iter: I, size: usize) -> Self {
+		assert!(size != 0, "size must be non-zero");
+		Self {
+			iter,
+			size,
+			window: VecDeque::with_capacity(size),
+			done: false,
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator> Iterator for Cons<I> {
+	type Item = Vec<Rc<I::Item>>;
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done { return None; }
+		if self.window.len() == self.size {
+			self.window.pop_front();
+		}
+		while self.window.len() < self.size {
+			match self.iter.next() {
+				Some(item) => self.window.push_back(Rc::new(item)),
+				None => {
+					self.done = true;
+					self.window.clear();
+					return None;
+				}
+			}
+		}
+		Some(self.window.iter().cloned().collect())
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		if self.done { return (0, Some(0)); }
+		// Until the first window is filled, `size - 1` items are needed
+		// before anything is yielded; afterwards each item is one window.
+		let missing = self.size - 1 - self.window.len().min(self.size - 1);
+		let (lower, upper) = self.iter.size_hint();
+		(
+			lower.saturating_sub(missing),
+			upper.map(|upper| upper.saturating_sub(missing)),
+		)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<I: ExactSizeIterator> ExactSizeIterator for Cons<I> {}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator> FusedIterator for Cons<I> {}
+
+/// Iterator over owned runs of identical items, see
+/// [`ConsIterator::cons_group`].
+///
+/// The first item of the next run is read ahead and kept until the
+/// following call to `next`.
+#[cfg(feature = "alloc")]
+pub struct ConsGroupIter<I: Iterator> {
+	iter: core::iter::Fuse<I>,
+	pending: Option<I::Item>,
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator> ConsGroupIter<I> {
+	fn new(iter: I) -> Self {
+		Self {
+			iter: iter.fuse(),
+			pending: None,
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator> Iterator for ConsGroupIter<I>
+where I::Item: Eq {
+	type Item = Vec<I::Item>;
+	fn next(&mut self) -> Option<Self::Item> {
+		let first = self.pending.take().or_else(|| self.iter.next())?;
+		let mut run = alloc::vec![first];
+		for item in self.iter.by_ref() {
+			if item != run[0] {
+				self.pending = Some(item);
+				break;
+			}
+			run.push(item);
+		}
+		Some(run)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let pending = self.pending.is_some() as usize;
+		let (lower, upper) = self.iter.size_hint();
+		(
+			(pending + lower).min(1),
+			upper.and_then(|upper| upper.checked_add(pending)),
+		)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator> FusedIterator for ConsGroupIter<I>
+where I::Item: Eq {}
+
+/// Add this into scope to give your slices the `cons_group()` method.
+///
+/// Other slice owners get every method by implementing
+/// [`ConsGroupExt::as_group_slice`] and [`ConsGroupExt::as_group_slice_mut`].
 ///
 /// # Example
 ///
+/// ```
+/// use each_cons::ConsGroupExt;
+///
+/// let slice = [1, 1, 2, 3, 3];
+/// let runs: Vec<&[i32]> = slice.cons_group().collect();
+/// assert_eq!(runs, [&[1, 1][..], &[2], &[3, 3]]);
+/// ```
+pub trait ConsGroupExt<T> {
+	/// Slice grouped by the other methods, which are all provided on top of
+	/// this one and [`ConsGroupExt::as_group_slice_mut`].
+	fn as_group_slice(&self) -> &[T];
+
+	/// Mutable slice grouped by [`ConsGroupExt::cons_group_mut`].
+	fn as_group_slice_mut(&mut self) -> &mut [T];
+
+	fn cons_group(&self) -> ConsGroup<'_, T>
+	where T: Eq {
+		ConsGroup::new(self.as_group_slice())
+	}
+
+	/// Same as [`ConsGroupExt::cons_group`], but yields mutable runs.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsGroupExt;
+	///
+	/// let mut slice = [3, 3, 1, 2, 2, 2];
+	/// for run in slice.cons_group_mut() {
+	///     run[1..].fill(0);
+	/// }
+	/// assert_eq!(slice, [3, 0, 1, 2, 0, 0]);
+	/// ```
+	fn cons_group_mut(&mut self) -> ConsGroupMut<'_, T>
+	where T: Eq {
+		ConsGroupMut::new(self.as_group_slice_mut())
+	}
+
+	/// Port of ruby's `each_cons(N)` for slices, yielding every window of
+	/// `N` consecutive elements as an array reference.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsGroupExt;
+	///
+	/// let slice = [1, 2, 4, 7];
+	/// let gaps: Vec<i32> = slice.each_cons_array().map(|[a, b]| b - a).collect();
+	/// assert_eq!(gaps, [1, 2, 3]);
+	/// ```
+	///
+	/// # Panics
+	///
+	/// Panics if `N` is 0.
+	fn each_cons_array<const N: usize>(&self) -> ConsArray<'_, T, N> {
+		ConsArray::new(self.as_group_slice())
+	}
+
+	/// Same as [`ConsGroupExt::cons_group`], but yields each run along with
+	/// its position in the slice.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsGroupExt;
+	///
+	/// let states = ['a', 'a', 'b', 'b', 'b'];
+	/// let timestamps = [10, 12, 13, 15, 20];
+	/// for run in states.cons_group_indexed() {
+	///     let during = timestamps[run.end - 1] - timestamps[run.start];
+	///     println!("{} for {}s", run.value(), during);
+	/// }
+	/// let ranges: Vec<_> = states.cons_group_indexed().map(|run| run.range()).collect();
+	/// assert_eq!(ranges, [0..2, 2..5]);
+	/// ```
+	fn cons_group_indexed(&self) -> ConsGroupIndexed<'_, T>
+	where T: Eq {
+		ConsGroupIndexed::new(self.as_group_slice())
+	}
+
+	/// Yields the first element of each run, like `Vec::dedup` but without
+	/// modifying nor copying the slice.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsGroupExt;
+	///
+	/// let slice = [1, 1, 2, 3, 3, 1];
+	/// let unique: Vec<&i32> = slice.unique_consecutive().collect();
+	/// assert_eq!(unique, [&1, &2, &3, &1]);
+	/// ```
+	fn unique_consecutive(&self) -> UniqueConsecutive<'_, T>
+	where T: Eq {
+		UniqueConsecutive::new(self.as_group_slice())
+	}
+
+	/// Same as [`ConsGroupExt::cons_group`] for primitive types, but finds
+	/// run boundaries by comparing chunks of elements at once. The gain
+	/// grows with run length, runs shorter than 32 elements being scanned
+	/// one element at a time as with `cons_group`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsGroupExt;
+	///
+	/// let row = [0u8, 0, 0, 0, 255, 255, 0];
+	/// let runs: Vec<&[u8]> = row.cons_group_fast().collect();
+	/// assert_eq!(runs, row.cons_group().collect::<Vec<_>>());
+	/// ```
+	fn cons_group_fast(&self) -> ConsGroupFast<'_, T>
+	where T: FastGroup {
+		ConsGroupFast::new(self.as_group_slice())
+	}
+
+	/// Groups consecutive elements for which `same(first, element)` holds,
+	/// where `first` is the first element of the current run.
+	///
+	/// Comparing against the first element bounds how far a run can drift
+	/// under a non-transitive relation such as a tolerance. To compare each
+	/// element against the previous one instead, use
+	/// [`ConsGroupExt::chunk_while`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsGroupExt;
+	///
+	/// let readings = [1.0, 1.05, 1.1, 1.15, 2.0];
+	/// let close = |a: &f64, b: &f64| (a - b).abs() < 0.12;
+	/// let by_first: Vec<&[f64]> = readings.cons_group_by(close).collect();
+	/// assert_eq!(by_first, [&[1.0, 1.05, 1.1][..], &[1.15], &[2.0]]);
+	/// let by_previous: Vec<&[f64]> = readings.chunk_while(close).collect();
+	/// assert_eq!(by_previous, [&[1.0, 1.05, 1.1, 1.15][..], &[2.0]]);
+	/// ```
+	fn cons_group_by<F>(&self, same: F) -> ConsGroupBy<'_, T, F>
+	where F: FnMut(&T, &T) -> bool {
+		ConsGroupBy::new(self.as_group_slice(), same)
+	}
+
+	/// Groups consecutive elements sharing the same key, yielding each key
+	/// along with its run.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsGroupExt;
+	///
+	/// let readings = [(1, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+	/// let mut days = readings.cons_group_by_key(|&(day, _)| day);
+	/// assert_eq!(days.next(), Some((1, &readings[0..2])));
+	/// assert_eq!(days.next(), Some((2, &readings[2..3])));
+	/// assert_eq!(days.next(), Some((1, &readings[3..4])));
+	/// assert_eq!(days.next(), None);
+	/// ```
+	fn cons_group_by_key<K, F>(&self, key: F) -> ConsGroupByKey<'_, T, K, F>
+	where
+		F: FnMut(&T) -> K,
+		K: PartialEq {
+		ConsGroupByKey::new(self.as_group_slice(), key)
+	}
+
+	/// Port of ruby's [`Enumerable#chunk_while`](https://rubydoc.info/stdlib/core/Enumerable:chunk_while):
+	/// keeps adjacent elements `a, b` in the same run as long as
+	/// `predicate(a, b)` holds.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsGroupExt;
+	///
+	/// let slice = [1, 2, 4, 9, 10, 11, 12, 15];
+	/// let ascending: Vec<&[i32]> = slice.chunk_while(|a, b| b == &(a + 1)).collect();
+	/// assert_eq!(ascending, [&[1, 2][..], &[4], &[9, 10, 11, 12], &[15]]);
+	/// ```
+	fn chunk_while<P>(&self, predicate: P) -> ChunkWhile<'_, T, P>
+	where P: FnMut(&T, &T) -> bool {
+		ChunkWhile::new(self.as_group_slice(), predicate)
+	}
+
+	/// Port of ruby's [`Enumerable#slice_when`](https://rubydoc.info/stdlib/core/Enumerable:slice_when):
+	/// splits between adjacent elements `a, b` wherever `predicate(a, b)`
+	/// holds.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsGroupExt;
+	///
+	/// let timestamps = [100, 101, 103, 160, 161, 300];
+	/// let sessions: Vec<&[i32]> = timestamps.slice_when(|a, b| b - a > 30).collect();
+	/// assert_eq!(sessions, [&[100, 101, 103][..], &[160, 161], &[300]]);
+	/// ```
+	fn slice_when<P>(&self, predicate: P) -> SliceWhen<'_, T, P>
+	where P: FnMut(&T, &T) -> bool {
+		SliceWhen::new(self.as_group_slice(), predicate)
+	}
+
+	/// Port of ruby's [`Enumerable#slice_before`](https://rubydoc.info/stdlib/core/Enumerable:slice_before):
+	/// starts a new slice at each element for which `predicate` holds.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsGroupExt;
+	///
+	/// let lines = ["BEGIN", "a", "b", "BEGIN", "c"];
+	/// let records: Vec<&[&str]> = lines.slice_before(|l| l.starts_with("BEGIN")).collect();
+	/// assert_eq!(records, [&["BEGIN", "a", "b"][..], &["BEGIN", "c"]]);
+	/// ```
+	fn slice_before<P>(&self, predicate: P) -> SliceBefore<'_, T, P>
+	where P: FnMut(&T) -> bool {
+		SliceBefore::new(self.as_group_slice(), predicate)
+	}
+
+	/// Port of ruby's [`Enumerable#chunk`](https://rubydoc.info/stdlib/core/Enumerable:chunk):
+	/// groups consecutive elements by the key returned by `key`, which may
+	/// also drop an element or put it in a chunk of its own, see [`Chunk`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::{Chunk, ConsGroupExt};
+	///
+	/// let words = ["apple", "avocado", "", "banana", "blueberry", "apricot"];
+	/// let by_letter: Vec<(char, &[&str])> = words
+	///     .chunk(|w| match w.chars().next() {
+	///         Some(c) => Chunk::Key(c),
+	///         None => Chunk::Separator,
+	///     })
+	///     .collect();
+	/// assert_eq!(by_letter, [
+	///     ('a', &words[0..2]),
+	///     ('b', &words[3..5]),
+	///     ('a', &words[5..6]),
+	/// ]);
+	/// ```
+	fn chunk<K, F>(&self, key: F) -> Chunked<'_, T, K, F>
+	where
+		F: FnMut(&T) -> Chunk<K>,
+		K: PartialEq {
+		Chunked::new(self.as_group_slice(), key)
+	}
+
+	/// Port of ruby's [`Enumerable#slice_after`](https://rubydoc.info/stdlib/core/Enumerable:slice_after):
+	/// ends a slice at each element for which `predicate` holds.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::ConsGroupExt;
+	///
+	/// let lines = ["a", "b;", "c;", "d"];
+	/// let statements: Vec<&[&str]> = lines.slice_after(|l| l.ends_with(';')).collect();
+	/// assert_eq!(statements, [&["a", "b;"][..], &["c;"], &["d"]]);
+	/// ```
+	fn slice_after<P>(&self, predicate: P) -> SliceAfter<'_, T, P>
+	where P: FnMut(&T) -> bool {
+		SliceAfter::new(self.as_group_slice(), predicate)
+	}
+}
+
+/// A run along with its position in the slice it was taken from.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Run<'a, T> {
+	/// Index of the first element of the run.
+	pub start: usize,
+	/// Index following the last element of the run.
+	pub end: usize,
+	pub slice: &'a [T],
+}
+
+impl<'a, T> Run<'a, T> {
+	pub fn range(&self) -> Range<usize> {
+		self.start..self.end
+	}
+
+	pub fn len(&self) -> usize {
+		self.slice.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.slice.is_empty()
+	}
+
+	/// First element of the run.
+	///
+	/// # Panics
+	///
+	/// Panics if the run is empty, which runs yielded by this crate never
+	/// are.
+	pub fn value(&self) -> &'a T {
+		&self.slice[0]
+	}
+}
+
+impl<'a, T> Clone for Run<'a, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<'a, T> Copy for Run<'a, T> {}
+
+/// Length of the run at the start of `slice`: its first element followed by
+/// every element for which `continues(previous, current)` holds.
+fn run_len<T>(slice: &[T], mut continues: impl FnMut(&T, &T) -> bool) -> usize {
+	slice
+		.windows(2)
+		.position(|pair| !continues(&pair[0], &pair[1]))
+		.map_or(slice.len(), |i| i + 1)
+}
+
+/// Length of the run at the end of `slice`, the mirror of [`run_len`].
+fn run_len_back<T>(slice: &[T], mut continues: impl FnMut(&T, &T) -> bool) -> usize {
+	slice
+		.windows(2)
+		.rev()
+		.position(|pair| !continues(&pair[0], &pair[1]))
+		.map_or(slice.len(), |i| i + 1)
+}
+
+/// Key of the first element of `slice` and length of the run it starts,
+/// made of the following elements for which `continues(first_key, key)`
+/// holds.
+///
+/// The key of the element ending the run is stored in `pending` and taken
+/// as the first key on the next call, with the rest of the slice, so that
+/// `key` is called once per element.
+fn keyed_run<T, K>(
+	slice: &[T],
+	pending: &mut Option<K>,
+	mut key: impl FnMut(&T) -> K,
+	mut continues: impl FnMut(&K, &K) -> bool,
+) -> Option<(K, usize)> {
+	let first = slice.first()?;
+	let first_key = pending.take().unwrap_or_else(|| key(first));
+	let len = run_len(slice, |_, b| {
+		let b_key = key(b);
+		let continued = continues(&first_key, &b_key);
+		if !continued { *pending = Some(b_key); }
+		continued
+	});
+	Some((first_key, len))
+}
 
 #[doc(hidden)]
 pub struct ConsGroup<'a, T> {
@@ -40,10 +741,13 @@ where T: Eq {
 	}
 }
 
-impl<T> ConsGroupExt<T> for [T]
-where T: Eq {
-	fn cons_group(&self) -> ConsGroup<'_, T> {
-		ConsGroup::new(self)
+impl<T> ConsGroupExt<T> for [T] {
+	fn as_group_slice(&self) -> &[T] {
+		self
+	}
+
+	fn as_group_slice_mut(&mut self) -> &mut [T] {
+		self
 	}
 }
 
@@ -51,16 +755,208 @@ impl<'a, T> Iterator for ConsGroup<'a, T>
 where T: Eq {
 	type Item = &'a [T];
 	fn next(&mut self) -> Option<Self::Item> {
-		let len = self.remaining.len();
-		if len == 0 { return None; }
-		let val = &self.remaining[0];
-		let mut i = 1;
-		while i < len - 1 && &self.remaining[i] == val {
-			i += 1;
+		if self.remaining.is_empty() { return None; }
+		let len = run_len(self.remaining, |a, b| a == b);
+		let (run, remaining) = self.remaining.split_at(len);
+		self.remaining = remaining;
+		Some(run)
+	}
+}
+
+impl<'a, T> DoubleEndedIterator for ConsGroup<'a, T>
+where T: Eq {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.remaining.is_empty() { return None; }
+		let len = run_len_back(self.remaining, |a, b| a == b);
+		let (remaining, run) = self.remaining.split_at(self.remaining.len() - len);
+		self.remaining = remaining;
+		Some(run)
+	}
+}
+
+/// Iterator over runs of identical elements and their positions, see
+/// [`ConsGroupExt::cons_group_indexed`].
+pub struct ConsGroupIndexed<'a, T> {
+	runs: ConsGroup<'a, T>,
+	start: usize,
+	end: usize,
+}
+
+impl<'a, T> ConsGroupIndexed<'a, T>
+where T: Eq {
+	fn new(slice: &'a [T]) -> Self {
+		Self {
+			runs: ConsGroup::new(slice),
+			start: 0,
+			end: slice.len(),
 		}
-		let slice_to_return = &self.remaining[0..i];
-		self.remaining = &self.remaining[i..];
-		Some(slice_to_return)
+	}
+}
+
+impl<'a, T> Iterator for ConsGroupIndexed<'a, T>
+where T: Eq {
+	type Item = Run<'a, T>;
+	fn next(&mut self) -> Option<Self::Item> {
+		let slice = self.runs.next()?;
+		let start = self.start;
+		self.start += slice.len();
+		Some(Run { start, end: self.start, slice })
+	}
+}
+
+impl<'a, T> DoubleEndedIterator for ConsGroupIndexed<'a, T>
+where T: Eq {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let slice = self.runs.next_back()?;
+		let end = self.end;
+		self.end -= slice.len();
+		Some(Run { start: self.end, end, slice })
+	}
+}
+
+/// Iterator over mutable runs of identical elements, see
+/// [`ConsGroupExt::cons_group_mut`].
+pub struct ConsGroupMut<'a, T> {
+	remaining: &'a mut [T]
+}
+
+impl<'a, T> ConsGroupMut<'a, T>
+where T: Eq {
+	fn new(slice: &'a mut [T]) -> Self {
+		Self {
+			remaining: slice
+		}
+	}
+}
+
+impl<'a, T> Iterator for ConsGroupMut<'a, T>
+where T: Eq {
+	type Item = &'a mut [T];
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.remaining.is_empty() { return None; }
+		let len = run_len(self.remaining, |a, b| a == b);
+		let (run, remaining) = core::mem::take(&mut self.remaining).split_at_mut(len);
+		self.remaining = remaining;
+		Some(run)
+	}
+}
+
+/// Iterator over runs of elements matching their run's first element, see
+/// [`ConsGroupExt::cons_group_by`].
+pub struct ConsGroupBy<'a, T, F> {
+	remaining: &'a [T],
+	same: F,
+}
+
+impl<'a, T, F> ConsGroupBy<'a, T, F> {
+	fn new(slice: &'a [T], same: F) -> Self {
+		Self {
+			remaining: slice,
+			same,
+		}
+	}
+}
+
+impl<'a, T, F> Iterator for ConsGroupBy<'a, T, F>
+where F: FnMut(&T, &T) -> bool {
+	type Item = &'a [T];
+	fn next(&mut self) -> Option<Self::Item> {
+		let first = self.remaining.first()?;
+		let same = &mut self.same;
+		let len = run_len(self.remaining, |_, b| same(first, b));
+		let (run, remaining) = self.remaining.split_at(len);
+		self.remaining = remaining;
+		Some(run)
+	}
+}
+
+/// Iterator over runs of elements sharing the same key, see
+/// [`ConsGroupExt::cons_group_by_key`].
+pub struct ConsGroupByKey<'a, T, K, F> {
+	remaining: &'a [T],
+	key: F,
+	/// Key of the element starting `remaining`, see [`keyed_run`].
+	pending_key: Option<K>,
+}
+
+impl<'a, T, K, F> ConsGroupByKey<'a, T, K, F> {
+	fn new(slice: &'a [T], key: F) -> Self {
+		Self {
+			remaining: slice,
+			key,
+			pending_key: None,
+		}
+	}
+}
+
+impl<'a, T, K, F> Iterator for ConsGroupByKey<'a, T, K, F>
+where
+	F: FnMut(&T) -> K,
+	K: PartialEq {
+	type Item = (K, &'a [T]);
+	fn next(&mut self) -> Option<Self::Item> {
+		let (run_key, len) =
+			keyed_run(self.remaining, &mut self.pending_key, &mut self.key, |a, b| a == b)?;
+		let (run, remaining) = self.remaining.split_at(len);
+		self.remaining = remaining;
+		Some((run_key, run))
+	}
+}
+
+/// Iterator over runs of adjacent elements satisfying a predicate, see
+/// [`ConsGroupExt::chunk_while`].
+pub struct ChunkWhile<'a, T, P> {
+	remaining: &'a [T],
+	predicate: P,
+}
+
+impl<'a, T, P> ChunkWhile<'a, T, P> {
+	fn new(slice: &'a [T], predicate: P) -> Self {
+		Self {
+			remaining: slice,
+			predicate,
+		}
+	}
+}
+
+impl<'a, T, P> Iterator for ChunkWhile<'a, T, P>
+where P: FnMut(&T, &T) -> bool {
+	type Item = &'a [T];
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.remaining.is_empty() { return None; }
+		let len = run_len(self.remaining, &mut self.predicate);
+		let (run, remaining) = self.remaining.split_at(len);
+		self.remaining = remaining;
+		Some(run)
+	}
+}
+
+/// Iterator over slices split between adjacent elements satisfying a
+/// predicate, see [`ConsGroupExt::slice_when`].
+pub struct SliceWhen<'a, T, P> {
+	remaining: &'a [T],
+	predicate: P,
+}
+
+impl<'a, T, P> SliceWhen<'a, T, P> {
+	fn new(slice: &'a [T], predicate: P) -> Self {
+		Self {
+			remaining: slice,
+			predicate,
+		}
+	}
+}
+
+impl<'a, T, P> Iterator for SliceWhen<'a, T, P>
+where P: FnMut(&T, &T) -> bool {
+	type Item = &'a [T];
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.remaining.is_empty() { return None; }
+		let predicate = &mut self.predicate;
+		let len = run_len(self.remaining, |a, b| !predicate(a, b));
+		let (run, remaining) = self.remaining.split_at(len);
+		self.remaining = remaining;
+		Some(run)
 	}
 }
 
@@ -77,9 +973,284 @@ mod tests {
 		assert!(matches!(cons.next(), Some(&[3, 3, 3])));
 		assert!(matches!(cons.next(), Some(&[4])));
 		assert!(matches!(cons.next(), Some(&[5])));
-		assert!(matches!(cons.next(), None));
-		assert!(matches!(cons.next(), None));
+		assert!(cons.next().is_none());
+		assert!(cons.next().is_none());
 	}
 
+	#[test]
+	fn keeps_trailing_runs_whole() {
+		let slice = [1, 2, 5, 5];
+		let runs: Vec<_> = slice.cons_group().collect();
+		assert_eq!(runs, [&[1][..], &[2], &[5, 5]]);
+		assert_eq!([1, 1].cons_group().collect::<Vec<_>>(), [&[1, 1]]);
+		assert_eq!([1, 1, 1].cons_group().collect::<Vec<_>>(), [&[1, 1, 1]]);
+		assert_eq!([1, 1].cons_group().rev().collect::<Vec<_>>(), [&[1, 1]]);
+		assert!([0; 0].cons_group().next().is_none());
+	}
 
+	#[test]
+	fn groups_from_the_back() {
+		let slice = [1, 1, 2, 3, 3, 3, 4, 5, 5];
+		let runs: Vec<_> = slice.cons_group().rev().collect();
+		assert_eq!(runs, [&[5, 5][..], &[4], &[3, 3, 3], &[2], &[1, 1]]);
+		assert_eq!([7, 7, 7].cons_group().next_back(), Some(&[7, 7, 7][..]));
+	}
+
+	#[test]
+	fn meets_in_the_middle() {
+		let slice = [1, 1, 2, 3, 3, 3, 4, 5, 5];
+		let mut cons = slice.cons_group();
+		assert_eq!(cons.next(), Some(&[1, 1][..]));
+		assert_eq!(cons.next_back(), Some(&[5, 5][..]));
+		assert_eq!(cons.next_back(), Some(&[4][..]));
+		assert_eq!(cons.next(), Some(&[2][..]));
+		assert_eq!(cons.next_back(), Some(&[3, 3, 3][..]));
+		assert!(cons.next().is_none());
+		assert!(cons.next_back().is_none());
+	}
+
+	#[test]
+	fn yields_run_positions() {
+		let slice = [1, 1, 2, 3, 3, 3, 4, 5, 5];
+		let ranges: Vec<_> = slice.cons_group_indexed().map(|run| run.range()).collect();
+		assert_eq!(ranges, [0..2, 2..3, 3..6, 6..7, 7..9]);
+		for run in slice.cons_group_indexed() {
+			assert_eq!(&slice[run.range()], run.slice);
+		}
+		let mut runs = slice.cons_group_indexed();
+		assert_eq!(runs.next_back().map(|run| run.range()), Some(7..9));
+		assert_eq!(runs.next().map(|run| run.range()), Some(0..2));
+		assert_eq!(runs.next_back().map(|run| run.range()), Some(6..7));
+		assert_eq!(runs.rev().map(|run| run.start).collect::<Vec<_>>(), [3, 2]);
+	}
+
+	#[test]
+	fn groups_mutably() {
+		let mut slice = [1, 1, 2, 3, 3, 3, 4, 5, 5];
+		let lens: Vec<_> = slice.cons_group_mut().map(|run| run.len()).collect();
+		assert_eq!(lens, [2, 1, 3, 1, 2]);
+		for (i, run) in slice.cons_group_mut().enumerate() {
+			for item in run.iter_mut() {
+				*item = i;
+			}
+		}
+		assert_eq!(slice, [0, 0, 1, 2, 2, 2, 3, 4, 4]);
+	}
+
+	#[test]
+	fn groups_by_comparator() {
+		let words = ["Foo", "FOO", "foo", "bar", "Bar", "foo"];
+		let runs: Vec<_> = words.cons_group_by(|a, b| a.eq_ignore_ascii_case(b)).collect();
+		assert_eq!(runs, [&words[0..3], &words[3..5], &words[5..6]]);
+		assert!([0; 0].cons_group_by(|_, _| true).next().is_none());
+	}
+
+	#[test]
+	fn compares_with_the_first_element_of_the_run() {
+		let slice = [0, 1, 2, 3, 4, 5];
+		let runs: Vec<_> = slice.cons_group_by(|a: &i32, b: &i32| (a - b).abs() <= 1).collect();
+		assert_eq!(runs, [&[0, 1][..], &[2, 3], &[4, 5]]);
+		assert_eq!(slice.chunk_while(|a: &i32, b: &i32| (a - b).abs() <= 1).count(), 1);
+	}
+
+	#[test]
+	fn groups_by_key() {
+		#[derive(Debug, PartialEq)]
+		struct Record { user: u32, amount: f64 }
+		let records = [
+			Record { user: 1, amount: 1.5 },
+			Record { user: 1, amount: 2.0 },
+			Record { user: 2, amount: 0.5 },
+			Record { user: 3, amount: 4.0 },
+			Record { user: 3, amount: 1.0 },
+		];
+		let runs: Vec<_> = records.cons_group_by_key(|r| r.user).collect();
+		assert_eq!(runs, [(1, &records[0..2]), (2, &records[2..3]), (3, &records[3..5])]);
+	}
+
+	#[test]
+	fn computes_each_key_once() {
+		let slice = [1, 1, 2, 3, 3];
+		let mut calls = 0;
+		let runs = slice.cons_group_by_key(|n| {
+			calls += 1;
+			*n
+		});
+		assert_eq!(runs.count(), 3);
+		assert_eq!(calls, slice.len());
+	}
+
+	#[test]
+	fn chunks_while_predicate_holds() {
+		let slice = [1, 2, 4, 9, 10, 11, 12, 15, 16, 19, 20, 21];
+		let runs: Vec<_> = slice.chunk_while(|a, b| b == &(a + 1)).collect();
+		assert_eq!(runs, [&[1, 2][..], &[4], &[9, 10, 11, 12], &[15, 16], &[19, 20, 21]]);
+		assert!([0; 0].chunk_while(|_, _| true).next().is_none());
+	}
+
+	#[test]
+	fn compares_adjacent_elements() {
+		let slice = [1, 2, 3, 2, 3, 1];
+		let runs: Vec<_> = slice.chunk_while(|a, b| a < b).collect();
+		assert_eq!(runs, [&[1, 2, 3][..], &[2, 3], &[1]]);
+	}
+
+	#[test]
+	fn slices_when_predicate_holds() {
+		let slice = [1, 2, 4, 9, 10, 11, 12, 15, 16, 19, 20, 21];
+		let runs: Vec<_> = slice.slice_when(|a, b| b != &(a + 1)).collect();
+		assert_eq!(runs, [&[1, 2][..], &[4], &[9, 10, 11, 12], &[15, 16], &[19, 20, 21]]);
+		assert_eq!([1, 1, 1].slice_when(|_, _| true).count(), 3);
+		assert_eq!([1, 1, 1].slice_when(|_, _| false).count(), 1);
+	}
+
+	#[cfg(feature = "alloc")]
+	fn values<T: Copy>(cons: Vec<Rc<T>>) -> Vec<T> {
+		cons.iter().map(|item| **item).collect()
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn yields_sliding_windows() {
+		let mut cons = (1..=4).each_cons(2);
+		assert_eq!(cons.next().map(values), Some(vec![1, 2]));
+		assert_eq!(cons.next().map(values), Some(vec![2, 3]));
+		assert_eq!(cons.next().map(values), Some(vec![3, 4]));
+		assert!(cons.next().is_none());
+		assert!(cons.next().is_none());
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn shares_items_between_windows() {
+		struct NotClone(u8);
+		let windows: Vec<_> = each_cons(2, vec![NotClone(1), NotClone(2), NotClone(3)]).collect();
+		assert!(Rc::ptr_eq(&windows[0][1], &windows[1][0]));
+		assert_eq!(windows[1][1].0, 3);
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn yields_nothing_when_shorter_than_size() {
+		assert!((1..3).each_cons(3).next().is_none());
+		assert_eq!((1..=3).each_cons(3).count(), 1);
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn reports_exact_size() {
+		let mut cons = (0..10).each_cons(3);
+		assert_eq!(cons.len(), 8);
+		cons.next();
+		assert_eq!(cons.len(), 7);
+		assert_eq!(cons.by_ref().count(), 7);
+		assert_eq!(cons.len(), 0);
+		assert_eq!((0..2).each_cons(3).len(), 0);
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn groups_iterator_items() {
+		let mut runs = vec![1, 1, 2, 3, 3, 3, 4, 5, 5].into_iter().cons_group();
+		assert_eq!(runs.size_hint(), (1, Some(9)));
+		assert_eq!(runs.next(), Some(vec![1, 1]));
+		assert_eq!(runs.size_hint(), (1, Some(7)));
+		assert_eq!(runs.next(), Some(vec![2]));
+		assert_eq!(runs.next(), Some(vec![3, 3, 3]));
+		assert_eq!(runs.next(), Some(vec![4]));
+		assert_eq!(runs.next(), Some(vec![5, 5]));
+		assert_eq!(runs.size_hint(), (0, Some(0)));
+		assert!(runs.next().is_none());
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn matches_slice_grouping() {
+		let slice = [0, 0, 1, 0, 2, 2, 2, 1, 1];
+		let owned: Vec<Vec<i32>> = slice.iter().copied().cons_group().collect();
+		let borrowed: Vec<Vec<i32>> = slice.cons_group().map(<[i32]>::to_vec).collect();
+		assert_eq!(owned, borrowed);
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	#[should_panic(expected = "size must be non-zero")]
+	fn rejects_zero_size() {
+		(0..10).each_cons(0);
+	}
+}
+
+#[cfg(test)]
+mod proptests {
+	use super::*;
+	use proptest::collection::vec;
+	use proptest::prelude::*;
+
+	/// Small values so that inputs are made of runs of various lengths.
+	fn slices() -> impl Strategy<Value = Vec<u8>> {
+		vec(0u8..3, 0..200)
+	}
+
+	proptest! {
+		#[test]
+		fn runs_concatenate_to_the_input(slice in slices()) {
+			let runs: Vec<&[u8]> = slice.cons_group().collect();
+			prop_assert_eq!(runs.concat(), slice);
+		}
+
+		#[test]
+		fn runs_are_homogeneous(slice in slices()) {
+			for run in slice.cons_group() {
+				prop_assert!(!run.is_empty());
+				prop_assert!(run.iter().all(|x| *x == run[0]));
+			}
+		}
+
+		#[test]
+		fn adjacent_runs_differ(slice in slices()) {
+			let runs: Vec<&[u8]> = slice.cons_group().collect();
+			for pair in runs.windows(2) {
+				prop_assert_ne!(pair[0][0], pair[1][0]);
+			}
+		}
+
+		#[test]
+		fn back_iteration_mirrors_front(slice in slices()) {
+			let mut forward: Vec<&[u8]> = slice.cons_group().collect();
+			forward.reverse();
+			let backward: Vec<&[u8]> = slice.cons_group().rev().collect();
+			prop_assert_eq!(backward, forward);
+		}
+
+		#[test]
+		fn front_and_back_meet(slice in slices(), sides in vec(any::<bool>(), 200)) {
+			let mut runs = slice.cons_group();
+			let (mut front, mut back) = (Vec::new(), Vec::new());
+			for from_front in sides {
+				let run = if from_front { runs.next() } else { runs.next_back() };
+				match run {
+					Some(run) if from_front => front.push(run),
+					Some(run) => back.push(run),
+					None => break,
+				}
+			}
+			front.extend(runs);
+			front.extend(back.into_iter().rev());
+			prop_assert_eq!(front, slice.cons_group().collect::<Vec<_>>());
+		}
+
+		#[test]
+		fn fast_grouping_matches(slice in slices()) {
+			let fast: Vec<&[u8]> = slice.cons_group_fast().collect();
+			prop_assert_eq!(fast, slice.cons_group().collect::<Vec<_>>());
+		}
+
+		#[cfg(feature = "alloc")]
+		#[test]
+		fn iterator_grouping_matches(slice in slices()) {
+			let owned: Vec<Vec<u8>> = slice.iter().copied().cons_group().collect();
+			let borrowed: Vec<Vec<u8>> = slice.cons_group().map(<[u8]>::to_vec).collect();
+			prop_assert_eq!(owned, borrowed);
+		}
+	}
 }
@@ -0,0 +1,119 @@
+//! Parallel run grouping with [rayon], behind the `rayon` feature.
+//!
+//! [rayon]: https://docs.rs/rayon
+
+use crate::{ConsGroup, ConsGroupExt};
+use rayon::iter::plumbing::{bridge_unindexed, Folder, UnindexedConsumer, UnindexedProducer};
+use rayon::iter::ParallelIterator;
+
+/// Add this into scope to give your slices the `par_cons_group()` method.
+///
+/// # Example
+///
+/// ```
+/// use each_cons::ParConsGroupExt;
+/// use rayon::iter::ParallelIterator;
+///
+/// let slice = [1, 1, 2, 3, 3, 3, 4, 5, 5];
+/// let runs: Vec<&[i32]> = slice.par_cons_group().collect();
+/// assert_eq!(runs, [&[1, 1][..], &[2], &[3, 3, 3], &[4], &[5, 5]]);
+/// ```
+pub trait ParConsGroupExt<T> {
+	/// Parallel counterpart of [`ConsGroupExt::cons_group`], yielding the
+	/// same runs in the same order.
+	fn par_cons_group(&self) -> ParConsGroup<'_, T>;
+}
+
+impl<T> ParConsGroupExt<T> for [T]
+where T: Eq + Sync {
+	fn par_cons_group(&self) -> ParConsGroup<'_, T> {
+		ParConsGroup {
+			slice: self
+		}
+	}
+}
+
+/// Parallel iterator over runs of identical elements, see
+/// [`ParConsGroupExt::par_cons_group`].
+///
+/// The slice is split in halves whose split point is moved to the closest
+/// run boundary, so that no run is ever cut in two.
+pub struct ParConsGroup<'a, T> {
+	slice: &'a [T],
+}
+
+impl<'a, T> ParallelIterator for ParConsGroup<'a, T>
+where T: Eq + Sync {
+	type Item = &'a [T];
+	fn drive_unindexed<C>(self, consumer: C) -> C::Result
+	where C: UnindexedConsumer<Self::Item> {
+		bridge_unindexed(self, consumer)
+	}
+}
+
+/// Index closest to `mid` at which a new run starts, if any.
+fn run_boundary<T: Eq>(slice: &[T], mid: usize) -> Option<usize> {
+	let is_boundary = |i: &usize| slice[i - 1] != slice[*i];
+	(mid..slice.len())
+		.find(is_boundary)
+		.or_else(|| (1..mid).rev().find(is_boundary))
+}
+
+impl<'a, T> UnindexedProducer for ParConsGroup<'a, T>
+where T: Eq + Sync {
+	type Item = &'a [T];
+
+	fn split(self) -> (Self, Option<Self>) {
+		if self.slice.len() < 2 { return (self, None); }
+		match run_boundary(self.slice, self.slice.len() / 2) {
+			Some(split) => {
+				let (left, right) = self.slice.split_at(split);
+				(Self { slice: left }, Some(Self { slice: right }))
+			}
+			None => (self, None),
+		}
+	}
+
+	fn fold_with<F>(self, folder: F) -> F
+	where F: Folder<Self::Item> {
+		let runs: ConsGroup<'a, T> = self.slice.cons_group();
+		folder.consume_iter(runs)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::prelude::*;
+
+	fn assert_same_runs<T: Eq + Sync + std::fmt::Debug>(slice: &[T]) {
+		let sequential: Vec<_> = slice.cons_group().collect();
+		let parallel: Vec<_> = slice.par_cons_group().collect();
+		assert_eq!(parallel, sequential);
+	}
+
+	#[test]
+	fn matches_sequential_grouping() {
+		assert_same_runs::<u8>(&[]);
+		assert_same_runs(&[1]);
+		assert_same_runs(&[1, 1]);
+		assert_same_runs(&[1, 1, 2, 3, 3, 3, 4, 5, 5]);
+		assert_same_runs(&[7; 1000]);
+	}
+
+	#[test]
+	fn never_cuts_runs() {
+		let slice: Vec<u32> = (0..100_000u32).map(|i| ((i / 3) ^ (i / 11)) % 4).collect();
+		assert_same_runs(&slice);
+		let slice: Vec<u32> = (0..100_000u32).map(|i| i / 997).collect();
+		assert_same_runs(&slice);
+	}
+
+	proptest! {
+		#[test]
+		fn parallel_grouping_matches(slice in proptest::collection::vec(0u8..3, 0..2000)) {
+			let parallel: Vec<&[u8]> = slice.par_cons_group().collect();
+			prop_assert_eq!(parallel, slice.cons_group().collect::<Vec<_>>());
+		}
+	}
+}
@@ -0,0 +1,401 @@
+//! Run-length encoding built on top of [`ConsGroup`].
+//!
+//! [`rle_encode`] lazily turns a slice into `(value, count)` pairs, while
+//! [`Rle`] owns the encoded runs and still allows random access to the
+//! decoded sequence, it needs the `alloc` feature. [`bytes`] stores runs
+//! of bytes in a compact binary format, it needs the `std` feature.
+//!
+#![cfg_attr(not(feature = "alloc"), doc = "[`Rle`]: ../index.html#features")]
+#![cfg_attr(not(feature = "std"), doc = "[`bytes`]: ../index.html#features")]
+
+use crate::{ConsGroup, ConsGroupExt};
+#[cfg(feature = "std")]
+pub mod bytes;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::iter::FusedIterator;
+
+/// Encodes a slice as `(value, count)` pairs, one per run of identical
+/// elements.
+///
+/// # Example
+///
+/// ```
+/// use each_cons::rle_encode;
+///
+/// let runs: Vec<(&char, usize)> = rle_encode(&['a', 'a', 'a', 'b', 'c', 'c']).collect();
+/// assert_eq!(runs, [(&'a', 3), (&'b', 1), (&'c', 2)]);
+/// ```
+pub fn rle_encode<T>(slice: &[T]) -> RleEncode<'_, T>
+where T: Eq {
+	RleEncode {
+		runs: slice.cons_group(),
+	}
+}
+
+/// Iterator over `(value, count)` pairs, see [`rle_encode`].
+pub struct RleEncode<'a, T> {
+	runs: ConsGroup<'a, T>,
+}
+
+impl<'a, T> Iterator for RleEncode<'a, T>
+where T: Eq {
+	type Item = (&'a T, usize);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.runs.next().map(|run| (&run[0], run.len()))
+	}
+}
+
+impl<'a, T> DoubleEndedIterator for RleEncode<'a, T>
+where T: Eq {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.runs.next_back().map(|run| (&run[0], run.len()))
+	}
+}
+
+/// Owned run-length encoded sequence.
+///
+/// Each run stores its value once along with the logical index at which it
+/// ends, so [`Rle::get`] finds an element with a binary search over those
+/// prefix sums.
+///
+/// # Example
+///
+/// ```
+/// use each_cons::Rle;
+///
+/// let rle = Rle::from_slice(&[0, 0, 0, 0, 1, 1, 0]);
+/// assert_eq!(rle.len(), 7);
+/// assert_eq!(rle.run_count(), 3);
+/// assert_eq!(rle.get(4), Some(&1));
+/// assert_eq!(rle.decode(), [0, 0, 0, 0, 1, 1, 0]);
+/// ```
+///
+/// # Serde
+///
+/// With the `serde` feature, an `Rle` is (de)serialized as a sequence of
+/// `(value, count)` pairs, e.g. `[[0,4],[1,2],[0,1]]` in JSON for the
+/// example above. Deserialization merges adjacent equal runs and drops empty
+/// ones, so that runs stay maximal whatever the input.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rle<T> {
+	values: Vec<T>,
+	ends: Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Rle<T> {
+	/// Creates an empty sequence.
+	pub fn new() -> Self {
+		Self {
+			values: Vec::new(),
+			ends: Vec::new(),
+		}
+	}
+
+	/// Number of elements once decoded.
+	pub fn len(&self) -> usize {
+		self.ends.last().copied().unwrap_or(0)
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.ends.is_empty()
+	}
+
+	/// Number of runs stored.
+	pub fn run_count(&self) -> usize {
+		self.values.len()
+	}
+
+	/// Returns the element at `index` in the decoded sequence.
+	pub fn get(&self, index: usize) -> Option<&T> {
+		let run = self.ends.partition_point(|&end| end <= index);
+		self.values.get(run)
+	}
+
+	/// Iterates over `(value, count)` pairs.
+	pub fn runs(&self) -> Runs<'_, T> {
+		Runs {
+			values: self.values.iter(),
+			ends: self.ends.iter(),
+			start: 0,
+		}
+	}
+
+	/// Iterates over the decoded elements without allocating.
+	pub fn iter(&self) -> Iter<'_, T> {
+		Iter {
+			runs: self.runs(),
+			current: None,
+		}
+	}
+
+	/// Decodes the whole sequence.
+	pub fn decode(&self) -> Vec<T>
+	where T: Clone {
+		self.iter().cloned().collect()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Rle<T>
+where T: Eq {
+	/// Encodes `slice`, equivalent to collecting [`rle_encode`].
+	pub fn from_slice(slice: &[T]) -> Self
+	where T: Clone {
+		let mut rle = Self::new();
+		for (value, count) in rle_encode(slice) {
+			rle.push_run(value.clone(), count);
+		}
+		rle
+	}
+
+	/// Appends one element, extending the last run if it is equal.
+	pub fn push(&mut self, value: T) {
+		self.push_run(value, 1);
+	}
+
+	/// Appends `count` copies of `value`, extending the last run if it is
+	/// equal. Empty runs are ignored so that runs stay maximal.
+	///
+	/// # Panics
+	///
+	/// Panics if the decoded length overflows `usize`.
+	pub fn push_run(&mut self, value: T, count: usize) {
+		if count == 0 { return; }
+		let end = self.len().checked_add(count).expect("Rle length overflows usize");
+		if self.values.last() == Some(&value) {
+			*self.ends.last_mut().unwrap() = end;
+		} else {
+			self.values.push(value);
+			self.ends.push(end);
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Default for Rle<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Extend<T> for Rle<T>
+where T: Eq {
+	fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+		for value in iter {
+			self.push(value);
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::iter::FromIterator<T> for Rle<T>
+where T: Eq {
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+		let mut rle = Self::new();
+		rle.extend(iter);
+		rle
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> IntoIterator for &'a Rle<T> {
+	type Item = &'a T;
+	type IntoIter = Iter<'a, T>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+/// Iterator over the `(value, count)` pairs of an [`Rle`], see [`Rle::runs`].
+#[cfg(feature = "alloc")]
+pub struct Runs<'a, T> {
+	values: core::slice::Iter<'a, T>,
+	ends: core::slice::Iter<'a, usize>,
+	start: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> Iterator for Runs<'a, T> {
+	type Item = (&'a T, usize);
+	fn next(&mut self) -> Option<Self::Item> {
+		let value = self.values.next()?;
+		let end = *self.ends.next()?;
+		let count = end - self.start;
+		self.start = end;
+		Some((value, count))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.values.size_hint()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> ExactSizeIterator for Runs<'a, T> {}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> FusedIterator for Runs<'a, T> {}
+
+/// Iterator over the decoded elements of an [`Rle`], see [`Rle::iter`].
+#[cfg(feature = "alloc")]
+pub struct Iter<'a, T> {
+	runs: Runs<'a, T>,
+	current: Option<(&'a T, usize)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> Iterator for Iter<'a, T> {
+	type Item = &'a T;
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			match &mut self.current {
+				Some((value, count)) if *count > 0 => {
+					*count -= 1;
+					return Some(*value);
+				}
+				_ => self.current = Some(self.runs.next()?),
+			}
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+	use super::Rle;
+	use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+	use serde::ser::{Serialize, Serializer};
+	use core::fmt;
+	use core::marker::PhantomData;
+
+	impl<T> Serialize for Rle<T>
+	where T: Serialize {
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			serializer.collect_seq(self.runs())
+		}
+	}
+
+	impl<'de, T> Deserialize<'de> for Rle<T>
+	where T: Deserialize<'de> + Eq {
+		fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+			deserializer.deserialize_seq(RleVisitor(PhantomData))
+		}
+	}
+
+	struct RleVisitor<T>(PhantomData<T>);
+
+	impl<'de, T> Visitor<'de> for RleVisitor<T>
+	where T: Deserialize<'de> + Eq {
+		type Value = Rle<T>;
+
+		fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+			formatter.write_str("a sequence of (value, count) pairs")
+		}
+
+		fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+			let mut rle = Rle::new();
+			while let Some((value, count)) = seq.next_element::<(T, usize)>()? {
+				if rle.len().checked_add(count).is_none() {
+					return Err(A::Error::custom("run lengths overflow usize"));
+				}
+				rle.push_run(value, count);
+			}
+			Ok(rle)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn encodes_runs() {
+		let slice = [1, 1, 2, 3, 3, 3, 4, 5, 5];
+		let runs: Vec<_> = rle_encode(&slice).collect();
+		assert_eq!(runs, [(&1, 2), (&2, 1), (&3, 3), (&4, 1), (&5, 2)]);
+		assert_eq!(rle_encode(&slice).next_back(), Some((&5, 2)));
+		assert!(rle_encode::<u8>(&[]).next().is_none());
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn round_trips() {
+		let slices: [&[u8]; 5] = [&[], &[7], &[1, 1], &[1, 2, 2, 1, 1, 1], &[0, 0, 0, 0, 9, 0]];
+		for slice in slices.iter() {
+			let rle = Rle::from_slice(slice);
+			assert_eq!(rle.decode(), *slice);
+			assert_eq!(rle.len(), slice.len());
+			assert_eq!(rle.run_count(), slice.cons_group().count());
+			let runs: Vec<_> = rle.runs().collect();
+			let groups: Vec<_> = slice.cons_group().map(|run| (&run[0], run.len())).collect();
+			assert_eq!(runs, groups);
+			assert_eq!(slice.iter().copied().collect::<Rle<_>>(), rle);
+		}
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn accesses_by_logical_index() {
+		let slice = [4, 4, 4, 2, 8, 8];
+		let rle = Rle::from_slice(&slice);
+		for (i, value) in slice.iter().enumerate() {
+			assert_eq!(rle.get(i), Some(value));
+		}
+		assert_eq!(rle.get(slice.len()), None);
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn merges_pushed_runs() {
+		let mut rle = Rle::new();
+		rle.push_run('a', 2);
+		rle.push_run('b', 0);
+		rle.push_run('a', 1);
+		rle.push('c');
+		assert_eq!(rle.runs().collect::<Vec<_>>(), [(&'a', 3), (&'c', 1)]);
+		assert_eq!(rle.len(), 4);
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	#[should_panic(expected = "Rle length overflows usize")]
+	fn rejects_overflowing_runs() {
+		let mut rle = Rle::new();
+		rle.push_run(1, usize::MAX);
+		rle.push_run(2, 1);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serializes_as_pairs() {
+		let rle = Rle::from_slice(&[0, 0, 0, 0, 1, 1, 0]);
+		let json = serde_json::to_string(&rle).unwrap();
+		assert_eq!(json, "[[0,4],[1,2],[0,1]]");
+		assert_eq!(serde_json::from_str::<Rle<i32>>(&json).unwrap(), rle);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn normalizes_deserialized_runs() {
+		let rle: Rle<char> = serde_json::from_str(r#"[["a",1],["a",2],["b",0],["c",1]]"#).unwrap();
+		assert_eq!(rle.runs().collect::<Vec<_>>(), [(&'a', 3), (&'c', 1)]);
+		assert!(serde_json::from_str::<Rle<char>>(r#"[["a",-1]]"#).is_err());
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn rejects_overflowing_deserialized_runs() {
+		let json = format!("[[1,{}],[2,1]]", usize::MAX);
+		let error = serde_json::from_str::<Rle<i32>>(&json).unwrap_err();
+		assert!(error.to_string().contains("run lengths overflow usize"));
+	}
+}
@@ -0,0 +1,559 @@
+//! Binary encoding of byte runs, for persisting masks and bitmaps.
+//!
+//! Two layouts are available, see [`Format`]. Both are stable: data written
+//! by a version of this crate is decoded identically by every later one.
+//!
+//! # Varint
+//!
+//! Runs are written back to back with no header. Each run is its length as
+//! an unsigned LEB128 varint, seven bits per byte with the least
+//! significant group first and the high bit set on every byte but the last,
+//! followed by the repeated byte. Lengths are never zero and fit in a
+//! `u64`. For instance 300 zeros followed by a one are encoded as
+//! `AC 02 00 01 01`.
+//!
+//! # PackBits
+//!
+//! The layout used by TIFF and Apple's `PackBits`, a sequence of packets
+//! each starting with a header byte `n` read as an `i8`:
+//!
+//! - `0..=127`: the next `n + 1` bytes are copied as is,
+//! - `-127..=-1`: the next byte is repeated `1 - n` times,
+//! - `-128`: the packet is empty and ignored.
+//!
+//! The encoder writes runs of three bytes or more as repeat packets and
+//! gathers other bytes into literal packets, a run of two only getting its
+//! own repeat packet when no literal packet is open. The encoded size is at
+//! most one byte per 128 bytes above the input size.
+//!
+//! # Example
+//!
+//! ```
+//! use std::io::{Read, Write};
+//! use each_cons::rle::bytes::{Decoder, Encoder, Format};
+//!
+//! let mask = [[0u8; 300], [1; 300]].concat();
+//! let mut encoder = Encoder::new(Vec::new(), Format::Varint);
+//! encoder.write_all(&mask)?;
+//! let encoded = encoder.finish()?;
+//! assert_eq!(encoded, [0xac, 0x02, 0x00, 0xac, 0x02, 0x01]);
+//!
+//! let mut decoded = Vec::new();
+//! Decoder::new(&encoded[..], Format::Varint).read_to_end(&mut decoded)?;
+//! assert_eq!(decoded, mask);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use super::rle_encode;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, ErrorKind, Read, Write};
+
+/// Binary layout of encoded runs, see the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+	/// Varint run length followed by the repeated byte.
+	Varint,
+	/// Literal and repeat packets compatible with `PackBits`.
+	PackBits,
+}
+
+/// Why encoded input could not be decoded.
+///
+/// A [`Decoder`] reports it as the inner error of the [`io::Error`] it
+/// returns, of kind [`ErrorKind::UnexpectedEof`] for [`Corruption::Truncated`]
+/// and [`ErrorKind::InvalidData`] otherwise.
+///
+/// # Example
+///
+/// ```
+/// use each_cons::rle::bytes::{decode, Corruption, Format};
+///
+/// let error = decode(&[0xac, 0x02], Format::Varint).unwrap_err();
+/// let corruption = error.get_ref().and_then(|e| e.downcast_ref::<Corruption>());
+/// assert_eq!(corruption, Some(&Corruption::Truncated));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Corruption {
+	/// The input ends in the middle of a run or packet.
+	Truncated,
+	/// A varint run has a length of zero.
+	EmptyRun,
+	/// A varint run length does not fit in a `u64`.
+	LengthOverflow,
+	/// The decoded bytes would exceed the limit set with [`Decoder::limit`].
+	LimitExceeded,
+}
+
+impl fmt::Display for Corruption {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Corruption::Truncated => "encoded runs are truncated",
+			Corruption::EmptyRun => "encoded run has a length of zero",
+			Corruption::LengthOverflow => "encoded run length overflows a u64",
+			Corruption::LimitExceeded => "decoded bytes exceed the limit",
+		})
+	}
+}
+
+impl Error for Corruption {}
+
+impl From<Corruption> for io::Error {
+	fn from(corruption: Corruption) -> Self {
+		let kind = match corruption {
+			Corruption::Truncated => ErrorKind::UnexpectedEof,
+			_ => ErrorKind::InvalidData,
+		};
+		io::Error::new(kind, corruption)
+	}
+}
+
+/// Encodes `bytes` in the given format.
+///
+/// # Example
+///
+/// ```
+/// use each_cons::rle::bytes::{encode, Format};
+///
+/// assert_eq!(encode(b"aaab", Format::Varint), [3, b'a', 1, b'b']);
+/// assert_eq!(encode(b"aaab", Format::PackBits), [0xfe, b'a', 0, b'b']);
+/// ```
+pub fn encode(bytes: &[u8], format: Format) -> Vec<u8> {
+	let mut encoder = Encoder::new(Vec::new(), format);
+	encoder.write_all(bytes).expect("writing to a Vec cannot fail");
+	encoder.finish().expect("writing to a Vec cannot fail")
+}
+
+/// Decodes `encoded`, written in the given format.
+///
+/// A handful of bytes may encode runs of up to `u64::MAX` bytes: use
+/// [`decode_with_limit`] on untrusted input.
+pub fn decode(encoded: &[u8], format: Format) -> io::Result<Vec<u8>> {
+	decode_with_limit(encoded, format, u64::MAX)
+}
+
+/// Decodes `encoded`, written in the given format, failing with
+/// [`Corruption::LimitExceeded`] rather than producing more than `max_len`
+/// bytes.
+///
+/// # Example
+///
+/// ```
+/// use each_cons::rle::bytes::{decode_with_limit, Format};
+///
+/// let corrupt = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x00];
+/// assert!(decode_with_limit(&corrupt, Format::Varint, 1 << 20).is_err());
+/// ```
+pub fn decode_with_limit(encoded: &[u8], format: Format, max_len: u64) -> io::Result<Vec<u8>> {
+	let mut bytes = Vec::new();
+	Decoder::new(encoded, format).limit(max_len).read_to_end(&mut bytes)?;
+	Ok(bytes)
+}
+
+/// Writes the runs of the bytes written to it into another writer.
+///
+/// A run is only complete once a different byte is written, so the last
+/// one is held until [`Encoder::finish`], which must be called: neither
+/// [`Write::flush`] nor dropping the encoder write it.
+pub struct Encoder<W: Write> {
+	inner: W,
+	format: Format,
+	/// Run that may go on with the next write.
+	run: Option<(u8, u64)>,
+	/// Lone bytes making up the current `PackBits` literal packet.
+	literal: Vec<u8>,
+}
+
+impl<W: Write> Encoder<W> {
+	pub fn new(inner: W, format: Format) -> Self {
+		Self {
+			inner,
+			format,
+			run: None,
+			literal: Vec::new(),
+		}
+	}
+
+	pub fn get_ref(&self) -> &W {
+		&self.inner
+	}
+
+	/// Writes the last run and flushes, returning the inner writer.
+	pub fn finish(mut self) -> io::Result<W> {
+		if let Some(run) = self.run.take() {
+			self.write_run(run)?;
+		}
+		self.write_literal()?;
+		self.inner.flush()?;
+		Ok(self.inner)
+	}
+
+	fn write_run(&mut self, (value, mut count): (u8, u64)) -> io::Result<()> {
+		match self.format {
+			Format::Varint => {
+				let mut packet = [0; 11];
+				let len = write_varint(count, &mut packet);
+				packet[len] = value;
+				self.inner.write_all(&packet[..=len])
+			}
+			Format::PackBits => {
+				// Ending a literal packet for a repeat packet of two bytes
+				// costs a header byte and saves none.
+				if count == 2 && !self.literal.is_empty() {
+					self.push_literal(value)?;
+					return self.push_literal(value);
+				}
+				if count >= 2 { self.write_literal()?; }
+				while count >= 2 {
+					let len = count.min(128);
+					self.inner.write_all(&[(257 - len) as u8, value])?;
+					count -= len;
+				}
+				if count == 1 { self.push_literal(value)?; }
+				Ok(())
+			}
+		}
+	}
+
+	fn push_literal(&mut self, value: u8) -> io::Result<()> {
+		self.literal.push(value);
+		if self.literal.len() == 128 { self.write_literal()?; }
+		Ok(())
+	}
+
+	fn write_literal(&mut self) -> io::Result<()> {
+		if self.literal.is_empty() { return Ok(()); }
+		self.inner.write_all(&[(self.literal.len() - 1) as u8])?;
+		self.inner.write_all(&self.literal)?;
+		self.literal.clear();
+		Ok(())
+	}
+}
+
+impl<W: Write> Write for Encoder<W> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		for (&value, count) in rle_encode(buf) {
+			let count = count as u64;
+			match &mut self.run {
+				Some((run_value, run_count)) if *run_value == value => *run_count += count,
+				run => {
+					if let Some(previous) = run.replace((value, count)) {
+						self.write_run(previous)?;
+					}
+				}
+			}
+		}
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.inner.flush()
+	}
+}
+
+/// Reads the bytes encoded in another reader.
+///
+/// Headers are read one byte at a time, wrap unbuffered readers such as
+/// files in a [`BufReader`](std::io::BufReader). Decoding is not bounded by
+/// default, see [`Decoder::limit`].
+pub struct Decoder<R: Read> {
+	inner: R,
+	format: Format,
+	state: State,
+	/// Bytes announced by the headers read so far.
+	len: u64,
+	max_len: u64,
+}
+
+/// What is left of the run or packet being decoded.
+enum State {
+	Idle,
+	Repeat { value: u8, remaining: u64 },
+	Literal { remaining: usize },
+}
+
+impl<R: Read> Decoder<R> {
+	pub fn new(inner: R, format: Format) -> Self {
+		Self {
+			inner,
+			format,
+			state: State::Idle,
+			len: 0,
+			max_len: u64::MAX,
+		}
+	}
+
+	/// Fails with [`Corruption::LimitExceeded`] rather than producing more
+	/// than `max_len` bytes. The limit is checked against each header, before
+	/// any byte of its run or packet is produced.
+	pub fn limit(mut self, max_len: u64) -> Self {
+		self.max_len = max_len;
+		self
+	}
+
+	pub fn get_ref(&self) -> &R {
+		&self.inner
+	}
+
+	pub fn into_inner(self) -> R {
+		self.inner
+	}
+
+	/// Reads the next header into `state`, returning `false` at the end of
+	/// the input.
+	fn read_header(&mut self) -> io::Result<bool> {
+		let first = match self.read_byte()? {
+			Some(byte) => byte,
+			None => return Ok(false),
+		};
+		self.state = match self.format {
+			Format::Varint => {
+				let mut len = u64::from(first & 0x7f);
+				let mut byte = first;
+				let mut shift = 7;
+				while byte & 0x80 != 0 {
+					byte = self.read_byte()?.ok_or(Corruption::Truncated)?;
+					let bits = u64::from(byte & 0x7f);
+					if shift >= 64 || (bits << shift) >> shift != bits {
+						return Err(Corruption::LengthOverflow.into());
+					}
+					len |= bits << shift;
+					shift += 7;
+				}
+				if len == 0 { return Err(Corruption::EmptyRun.into()); }
+				let value = self.read_byte()?.ok_or(Corruption::Truncated)?;
+				State::Repeat {
+					value,
+					remaining: len,
+				}
+			}
+			Format::PackBits => match first as i8 {
+				-128 => State::Idle,
+				header @ 0..=127 => State::Literal {
+					remaining: header as usize + 1,
+				},
+				header => State::Repeat {
+					value: self.read_byte()?.ok_or(Corruption::Truncated)?,
+					remaining: (1 - i64::from(header)) as u64,
+				},
+			},
+		};
+		let len = match self.state {
+			State::Idle => 0,
+			State::Repeat { remaining, .. } => remaining,
+			State::Literal { remaining } => remaining as u64,
+		};
+		self.len = self
+			.len
+			.checked_add(len)
+			.filter(|&len| len <= self.max_len)
+			.ok_or(Corruption::LimitExceeded)?;
+		Ok(true)
+	}
+
+	fn read_byte(&mut self) -> io::Result<Option<u8>> {
+		let mut byte = [0];
+		loop {
+			match self.inner.read(&mut byte) {
+				Ok(0) => return Ok(None),
+				Ok(_) => return Ok(Some(byte[0])),
+				Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+				Err(e) => return Err(e),
+			}
+		}
+	}
+}
+
+impl<R: Read> Read for Decoder<R> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		if buf.is_empty() { return Ok(0); }
+		loop {
+			match self.state {
+				State::Idle => {
+					if !self.read_header()? { return Ok(0); }
+				}
+				State::Repeat { value, remaining } => {
+					let len = remaining.min(buf.len() as u64) as usize;
+					buf[..len].fill(value);
+					self.state = match remaining - len as u64 {
+						0 => State::Idle,
+						remaining => State::Repeat { value, remaining },
+					};
+					return Ok(len);
+				}
+				State::Literal { remaining } => {
+					let len = remaining.min(buf.len());
+					let read = self.inner.read(&mut buf[..len])?;
+					if read == 0 { return Err(Corruption::Truncated.into()); }
+					self.state = match remaining - read {
+						0 => State::Idle,
+						remaining => State::Literal { remaining },
+					};
+					return Ok(read);
+				}
+			}
+		}
+	}
+}
+
+/// Writes `n` as an unsigned LEB128 varint, returning its length.
+fn write_varint(mut n: u64, buf: &mut [u8]) -> usize {
+	let mut len = 0;
+	loop {
+		let byte = (n & 0x7f) as u8;
+		n >>= 7;
+		if n == 0 {
+			buf[len] = byte;
+			return len + 1;
+		}
+		buf[len] = byte | 0x80;
+		len += 1;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const FORMATS: [Format; 2] = [Format::Varint, Format::PackBits];
+
+	fn samples() -> Vec<Vec<u8>> {
+		let mut mixed = Vec::new();
+		for (i, len) in [1, 2, 3, 127, 128, 129, 130, 256, 1, 1, 1000].iter().enumerate() {
+			mixed.resize(mixed.len() + len, i as u8 % 3);
+		}
+		vec![
+			Vec::new(),
+			vec![42],
+			vec![7; 1000],
+			(0..300).map(|i| i as u8).collect(),
+			mixed,
+			b"aab".repeat(1000),
+			b"abbcccd".repeat(100),
+		]
+	}
+
+	fn corruption(error: &io::Error) -> Option<Corruption> {
+		error.get_ref()?.downcast_ref::<Corruption>().copied()
+	}
+
+	#[test]
+	fn writes_varint_runs() {
+		let bytes = [[0u8; 300].as_slice(), &[1]].concat();
+		assert_eq!(encode(&bytes, Format::Varint), [0xac, 0x02, 0x00, 0x01, 0x01]);
+		assert!(encode(&[], Format::Varint).is_empty());
+		let mut packet = [0; 11];
+		assert_eq!(write_varint(u64::MAX, &mut packet), 10);
+		let mut encoded = packet[..10].to_vec();
+		encoded.push(b'x');
+		let mut decoder = Decoder::new(&encoded[..], Format::Varint);
+		let mut start = [0; 4];
+		decoder.read_exact(&mut start).unwrap();
+		assert_eq!(&start, b"xxxx");
+	}
+
+	#[test]
+	fn reads_packbits_reference() {
+		let encoded = [
+			0xfe, 0xaa, 0x02, 0x80, 0x00, 0x2a, 0xfd, 0xaa, 0x03, 0x80, 0x00, 0x2a, 0x22, 0xf7, 0xaa,
+		];
+		let expected = [
+			0xaa, 0xaa, 0xaa, 0x80, 0x00, 0x2a, 0xaa, 0xaa, 0xaa, 0xaa, 0x80, 0x00, 0x2a, 0x22, 0xaa,
+			0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa,
+		];
+		assert_eq!(decode(&encoded, Format::PackBits).unwrap(), expected);
+		assert_eq!(decode(&[0x80, 0x00, b'a', 0x80], Format::PackBits).unwrap(), b"a");
+	}
+
+	#[test]
+	fn keeps_pairs_in_literal_packets() {
+		assert_eq!(encode(b"abbc", Format::PackBits), [0x03, b'a', b'b', b'b', b'c']);
+		assert_eq!(encode(b"bbc", Format::PackBits), [0xff, b'b', 0x00, b'c']);
+		assert_eq!(encode(b"abbbc", Format::PackBits), [0x00, b'a', 0xfe, b'b', 0x00, b'c']);
+	}
+
+	#[test]
+	fn round_trips() {
+		for format in FORMATS.iter().copied() {
+			for bytes in samples() {
+				let encoded = encode(&bytes, format);
+				assert_eq!(decode(&encoded, format).unwrap(), bytes, "{:?}", format);
+				if format == Format::PackBits {
+					assert!(encoded.len() <= bytes.len() + bytes.len().div_ceil(128));
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn streams_in_small_chunks() {
+		for format in FORMATS.iter().copied() {
+			for bytes in samples() {
+				let mut encoder = Encoder::new(Vec::new(), format);
+				for (i, chunk) in bytes.chunks(7).enumerate() {
+					let (a, b) = chunk.split_at(i % chunk.len().max(1));
+					encoder.write_all(a).unwrap();
+					encoder.write_all(b).unwrap();
+				}
+				let encoded = encoder.finish().unwrap();
+				assert_eq!(encoded, encode(&bytes, format));
+				let mut decoder = Decoder::new(&encoded[..], format);
+				let mut decoded = Vec::new();
+				let mut buf = [0; 5];
+				loop {
+					match decoder.read(&mut buf).unwrap() {
+						0 => break,
+						n => decoded.extend_from_slice(&buf[..n]),
+					}
+				}
+				assert_eq!(decoded, bytes);
+			}
+		}
+	}
+
+	#[test]
+	fn reports_truncated_input() {
+		let truncated: [(&[u8], Format); 5] = [
+			(&[0xac], Format::Varint),
+			(&[0xac, 0x02], Format::Varint),
+			(&[0x03, b'a', 0x02], Format::Varint),
+			(&[0xfe], Format::PackBits),
+			(&[0x02, b'a', b'b'], Format::PackBits),
+		];
+		for (encoded, format) in truncated.iter() {
+			let error = decode(encoded, *format).unwrap_err();
+			assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+			assert_eq!(corruption(&error), Some(Corruption::Truncated));
+		}
+	}
+
+	#[test]
+	fn reports_invalid_lengths() {
+		let error = decode(&[0x00, b'a'], Format::Varint).unwrap_err();
+		assert_eq!(error.kind(), ErrorKind::InvalidData);
+		assert_eq!(corruption(&error), Some(Corruption::EmptyRun));
+		let error = decode(&[0x80, 0x00, b'a'], Format::Varint).unwrap_err();
+		assert_eq!(corruption(&error), Some(Corruption::EmptyRun));
+		let mut overflowing = vec![0xff; 10];
+		overflowing.push(b'a');
+		let error = decode(&overflowing, Format::Varint).unwrap_err();
+		assert_eq!(error.kind(), ErrorKind::InvalidData);
+		assert_eq!(corruption(&error), Some(Corruption::LengthOverflow));
+	}
+
+	#[test]
+	fn enforces_the_limit() {
+		let mut maxed = vec![0xff; 9];
+		maxed.extend_from_slice(&[0x01, b'a']);
+		let error = decode_with_limit(&maxed, Format::Varint, 1 << 20).unwrap_err();
+		assert_eq!(error.kind(), ErrorKind::InvalidData);
+		assert_eq!(corruption(&error), Some(Corruption::LimitExceeded));
+		for format in FORMATS.iter().copied() {
+			let bytes = [[1u8; 200].as_slice(), b"abc"].concat();
+			let encoded = encode(&bytes, format);
+			assert_eq!(decode_with_limit(&encoded, format, 203).unwrap(), bytes);
+			let error = decode_with_limit(&encoded, format, 202).unwrap_err();
+			assert_eq!(corruption(&error), Some(Corruption::LimitExceeded));
+		}
+	}
+}
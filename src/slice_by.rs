@@ -0,0 +1,204 @@
+//! Ports of ruby's `Enumerable#slice_before` and `Enumerable#slice_after`.
+//!
+//! As with ruby, no empty group is ever yielded: an element matching at the
+//! start for `slice_before`, or at the end for `slice_after`, does not
+//! produce a leading or trailing empty group.
+
+use crate::run_len;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Iterator over slices starting at each element matching a predicate, see
+/// [`ConsGroupExt::slice_before`].
+///
+/// [`ConsGroupExt::slice_before`]: crate::ConsGroupExt::slice_before
+pub struct SliceBefore<'a, T, P> {
+	remaining: &'a [T],
+	predicate: P,
+}
+
+impl<'a, T, P> SliceBefore<'a, T, P> {
+	pub(crate) fn new(slice: &'a [T], predicate: P) -> Self {
+		Self {
+			remaining: slice,
+			predicate,
+		}
+	}
+}
+
+impl<'a, T, P> Iterator for SliceBefore<'a, T, P>
+where P: FnMut(&T) -> bool {
+	type Item = &'a [T];
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.remaining.is_empty() { return None; }
+		let predicate = &mut self.predicate;
+		let len = run_len(self.remaining, |_, b| !predicate(b));
+		let (group, remaining) = self.remaining.split_at(len);
+		self.remaining = remaining;
+		Some(group)
+	}
+}
+
+impl<'a, T, P> FusedIterator for SliceBefore<'a, T, P>
+where P: FnMut(&T) -> bool {}
+
+/// Iterator over slices ending at each element matching a predicate, see
+/// [`ConsGroupExt::slice_after`].
+///
+/// [`ConsGroupExt::slice_after`]: crate::ConsGroupExt::slice_after
+pub struct SliceAfter<'a, T, P> {
+	remaining: &'a [T],
+	predicate: P,
+}
+
+impl<'a, T, P> SliceAfter<'a, T, P> {
+	pub(crate) fn new(slice: &'a [T], predicate: P) -> Self {
+		Self {
+			remaining: slice,
+			predicate,
+		}
+	}
+}
+
+impl<'a, T, P> Iterator for SliceAfter<'a, T, P>
+where P: FnMut(&T) -> bool {
+	type Item = &'a [T];
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.remaining.is_empty() { return None; }
+		let predicate = &mut self.predicate;
+		let len = run_len(self.remaining, |a, _| !predicate(a));
+		let (group, remaining) = self.remaining.split_at(len);
+		self.remaining = remaining;
+		Some(group)
+	}
+}
+
+impl<'a, T, P> FusedIterator for SliceAfter<'a, T, P>
+where P: FnMut(&T) -> bool {}
+
+/// Iterator over owned groups starting at each item matching a predicate,
+/// see [`ConsIterator::slice_before`].
+///
+/// [`ConsIterator::slice_before`]: crate::ConsIterator::slice_before
+#[cfg(feature = "alloc")]
+pub struct SliceBeforeIter<I: Iterator, P> {
+	iter: core::iter::Fuse<I>,
+	predicate: P,
+	pending: Option<I::Item>,
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator, P> SliceBeforeIter<I, P> {
+	pub(crate) fn new(iter: I, predicate: P) -> Self {
+		Self {
+			iter: iter.fuse(),
+			predicate,
+			pending: None,
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator, P> Iterator for SliceBeforeIter<I, P>
+where P: FnMut(&I::Item) -> bool {
+	type Item = Vec<I::Item>;
+	fn next(&mut self) -> Option<Self::Item> {
+		let first = self.pending.take().or_else(|| self.iter.next())?;
+		let mut group = alloc::vec![first];
+		for item in self.iter.by_ref() {
+			if (self.predicate)(&item) {
+				self.pending = Some(item);
+				break;
+			}
+			group.push(item);
+		}
+		Some(group)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator, P> FusedIterator for SliceBeforeIter<I, P>
+where P: FnMut(&I::Item) -> bool {}
+
+/// Iterator over owned groups ending at each item matching a predicate, see
+/// [`ConsIterator::slice_after`].
+///
+/// [`ConsIterator::slice_after`]: crate::ConsIterator::slice_after
+#[cfg(feature = "alloc")]
+pub struct SliceAfterIter<I, P> {
+	iter: core::iter::Fuse<I>,
+	predicate: P,
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator, P> SliceAfterIter<I, P> {
+	pub(crate) fn new(iter: I, predicate: P) -> Self {
+		Self {
+			iter: iter.fuse(),
+			predicate,
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator, P> Iterator for SliceAfterIter<I, P>
+where P: FnMut(&I::Item) -> bool {
+	type Item = Vec<I::Item>;
+	fn next(&mut self) -> Option<Self::Item> {
+		let mut group = Vec::new();
+		for item in self.iter.by_ref() {
+			let last = (self.predicate)(&item);
+			group.push(item);
+			if last { break; }
+		}
+		if group.is_empty() { None } else { Some(group) }
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator, P> FusedIterator for SliceAfterIter<I, P>
+where P: FnMut(&I::Item) -> bool {}
+
+#[cfg(test)]
+mod tests {
+	use crate::ConsGroupExt;
+	#[cfg(feature = "alloc")]
+	use crate::ConsIterator;
+
+	#[test]
+	fn slices_before_matches() {
+		let lines = ["BEGIN 1", "a", "b", "BEGIN 2", "c", "BEGIN 3"];
+		let records: Vec<_> = lines.slice_before(|l| l.starts_with("BEGIN")).collect();
+		assert_eq!(records, [&lines[0..3], &lines[3..5], &lines[5..6]]);
+		let records: Vec<_> = lines[1..].slice_before(|l| l.starts_with("BEGIN")).collect();
+		assert_eq!(records, [&lines[1..3], &lines[3..5], &lines[5..6]]);
+		assert!([""; 0].slice_before(|_| true).next().is_none());
+		assert_eq!([1, 2, 3].slice_before(|_| true).count(), 3);
+	}
+
+	#[test]
+	fn slices_after_matches() {
+		let words = ["foo", "bar.", "baz", "qux.", "quux"];
+		let sentences: Vec<_> = words.slice_after(|w| w.ends_with('.')).collect();
+		assert_eq!(sentences, [&words[0..2], &words[2..4], &words[4..5]]);
+		let sentences: Vec<_> = words[..4].slice_after(|w| w.ends_with('.')).collect();
+		assert_eq!(sentences, [&words[0..2], &words[2..4]]);
+		assert!([""; 0].slice_after(|_| true).next().is_none());
+		assert_eq!([1, 2, 3].slice_after(|_| false).count(), 1);
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn matches_slice_grouping() {
+		let slice = [0, 3, 1, 0, 0, 2, 3, 0];
+		for predicate in [|n: &i32| *n == 0, |n: &i32| *n > 1, |_: &i32| true, |_: &i32| false].iter() {
+			let owned: Vec<Vec<i32>> = slice.iter().copied().slice_before(predicate).collect();
+			let borrowed: Vec<Vec<i32>> = slice.slice_before(predicate).map(<[i32]>::to_vec).collect();
+			assert_eq!(owned, borrowed);
+			let owned: Vec<Vec<i32>> = slice.iter().copied().slice_after(predicate).collect();
+			let borrowed: Vec<Vec<i32>> = slice.slice_after(predicate).map(<[i32]>::to_vec).collect();
+			assert_eq!(owned, borrowed);
+		}
+	}
+}
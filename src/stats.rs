@@ -0,0 +1,109 @@
+//! Summary statistics over the runs of a slice.
+
+use crate::{ConsGroupExt, Run};
+use alloc::collections::BTreeMap;
+
+/// Statistics about the runs of identical elements in a slice, computed in
+/// a single pass over [`ConsGroupExt::cons_group_indexed`].
+///
+/// # Example
+///
+/// ```
+/// use each_cons::RunStats;
+///
+/// let stats = RunStats::from_slice(&[1, 1, 2, 3, 3, 3, 4]);
+/// assert_eq!(stats.run_count(), 4);
+/// let longest = stats.longest().unwrap();
+/// assert_eq!((longest.start, longest.slice), (3, &[3, 3, 3][..]));
+/// assert_eq!(stats.mean_len(), Some(1.75));
+/// assert_eq!(stats.histogram()[&1], 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunStats<'a, T> {
+	run_count: usize,
+	len: usize,
+	longest: Option<Run<'a, T>>,
+	shortest: Option<Run<'a, T>>,
+	histogram: BTreeMap<usize, usize>,
+}
+
+impl<'a, T> RunStats<'a, T>
+where T: Eq {
+	/// Computes the statistics of the runs of `slice`.
+	pub fn from_slice(slice: &'a [T]) -> Self {
+		let mut stats = Self {
+			run_count: 0,
+			len: slice.len(),
+			longest: None,
+			shortest: None,
+			histogram: BTreeMap::new(),
+		};
+		for run in slice.cons_group_indexed() {
+			stats.run_count += 1;
+			*stats.histogram.entry(run.len()).or_insert(0) += 1;
+			if stats.longest.map_or(true, |longest| run.len() > longest.len()) {
+				stats.longest = Some(run);
+			}
+			if stats.shortest.map_or(true, |shortest| run.len() < shortest.len()) {
+				stats.shortest = Some(run);
+			}
+		}
+		stats
+	}
+}
+
+impl<'a, T> RunStats<'a, T> {
+	/// Number of runs.
+	pub fn run_count(&self) -> usize {
+		self.run_count
+	}
+
+	/// First of the longest runs, `None` for an empty slice.
+	pub fn longest(&self) -> Option<Run<'a, T>> {
+		self.longest
+	}
+
+	/// First of the shortest runs, `None` for an empty slice.
+	pub fn shortest(&self) -> Option<Run<'a, T>> {
+		self.shortest
+	}
+
+	/// Average run length, `None` for an empty slice.
+	pub fn mean_len(&self) -> Option<f64> {
+		if self.run_count == 0 { return None; }
+		Some(self.len as f64 / self.run_count as f64)
+	}
+
+	/// Number of runs for each run length.
+	pub fn histogram(&self) -> &BTreeMap<usize, usize> {
+		&self.histogram
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn summarizes_runs() {
+		let slice = ['a', 'b', 'b', 'c', 'c', 'c', 'd', 'e', 'e', 'e'];
+		let stats = RunStats::from_slice(&slice);
+		assert_eq!(stats.run_count(), 5);
+		assert_eq!(stats.longest(), Some(Run { start: 3, end: 6, slice: &slice[3..6] }));
+		assert_eq!(stats.longest().unwrap().value(), &'c');
+		assert_eq!(stats.shortest(), Some(Run { start: 0, end: 1, slice: &slice[0..1] }));
+		assert_eq!(stats.mean_len(), Some(2.0));
+		let histogram: Vec<_> = stats.histogram().iter().map(|(&len, &count)| (len, count)).collect();
+		assert_eq!(histogram, [(1, 2), (2, 1), (3, 2)]);
+	}
+
+	#[test]
+	fn handles_empty_slices() {
+		let stats = RunStats::<u8>::from_slice(&[]);
+		assert_eq!(stats.run_count(), 0);
+		assert_eq!(stats.longest(), None);
+		assert_eq!(stats.shortest(), None);
+		assert_eq!(stats.mean_len(), None);
+		assert!(stats.histogram().is_empty());
+	}
+}
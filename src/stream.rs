@@ -0,0 +1,226 @@
+//! Asynchronous counterparts of the iterator adapters, behind the `futures`
+//! feature.
+
+use alloc::{collections::VecDeque, sync::Arc, vec::Vec};
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+/// Add this into scope to give your streams the `each_cons(N)` and
+/// `cons_group()` methods.
+///
+/// # Example
+///
+/// ```
+/// use each_cons::ConsStreamExt;
+/// use futures::{executor::block_on, stream, StreamExt};
+///
+/// let events = stream::iter(vec!["up", "up", "down", "up"]);
+/// let runs: Vec<Vec<&str>> = block_on(events.cons_group().collect());
+/// assert_eq!(runs, [vec!["up", "up"], vec!["down"], vec!["up"]]);
+/// ```
+pub trait ConsStreamExt: Stream + Sized {
+	/// Returns a stream of every window of `size` consecutive items, see
+	/// [`ConsIterator::each_cons`].
+	///
+	/// Items are shared between windows through an [`Arc`] rather than an
+	/// `Rc`, so that the stream can be sent across tasks.
+	///
+	/// # Panics
+	///
+	/// Panics if `size` is 0.
+	///
+	/// [`ConsIterator::each_cons`]: crate::ConsIterator::each_cons
+	fn each_cons(self, size: usize) -> ConsStream<Self> {
+		ConsStream::new(self, size)
+	}
+
+	/// Groups consecutive identical items into owned runs, see
+	/// [`ConsIterator::cons_group`].
+	///
+	/// A run is only emitted once a differing item arrives or the stream
+	/// ends.
+	///
+	/// [`ConsIterator::cons_group`]: crate::ConsIterator::cons_group
+	fn cons_group(self) -> ConsGroupStream<Self>
+	where Self::Item: Eq {
+		ConsGroupStream::new(self)
+	}
+}
+
+impl<S: Stream> ConsStreamExt for S {}
+
+pin_project! {
+	/// Stream of windows of consecutive items, see
+	/// [`ConsStreamExt::each_cons`].
+	pub struct ConsStream<S: Stream> {
+		#[pin]
+		stream: S,
+		size: usize,
+		window: VecDeque<Arc<S::Item>>,
+		done: bool,
+	}
+}
+
+impl<S: Stream> ConsStream<S> {
+	fn new(stream: S, size: usize) -> Self {
+		assert!(size != 0, "size must be non-zero");
+		Self {
+			stream,
+			size,
+			window: VecDeque::with_capacity(size),
+			done: false,
+		}
+	}
+}
+
+impl<S: Stream> Stream for ConsStream<S> {
+	type Item = Vec<Arc<S::Item>>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let mut this = self.project();
+		if *this.done { return Poll::Ready(None); }
+		if this.window.len() == *this.size {
+			this.window.pop_front();
+		}
+		while this.window.len() < *this.size {
+			match this.stream.as_mut().poll_next(cx) {
+				Poll::Ready(Some(item)) => this.window.push_back(Arc::new(item)),
+				Poll::Ready(None) => {
+					*this.done = true;
+					this.window.clear();
+					return Poll::Ready(None);
+				}
+				Poll::Pending => return Poll::Pending,
+			}
+		}
+		Poll::Ready(Some(this.window.iter().cloned().collect()))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		if self.done { return (0, Some(0)); }
+		let missing = self.size - 1 - self.window.len().min(self.size - 1);
+		let (lower, upper) = self.stream.size_hint();
+		(
+			lower.saturating_sub(missing),
+			upper.map(|upper| upper.saturating_sub(missing)),
+		)
+	}
+}
+
+impl<S: Stream> FusedStream for ConsStream<S> {
+	fn is_terminated(&self) -> bool {
+		self.done
+	}
+}
+
+pin_project! {
+	/// Stream of owned runs of identical items, see
+	/// [`ConsStreamExt::cons_group`].
+	pub struct ConsGroupStream<S: Stream> {
+		#[pin]
+		stream: S,
+		run: Vec<S::Item>,
+		done: bool,
+	}
+}
+
+impl<S: Stream> ConsGroupStream<S> {
+	fn new(stream: S) -> Self {
+		Self {
+			stream,
+			run: Vec::new(),
+			done: false,
+		}
+	}
+}
+
+impl<S: Stream> Stream for ConsGroupStream<S>
+where S::Item: Eq {
+	type Item = Vec<S::Item>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let mut this = self.project();
+		while !*this.done {
+			match this.stream.as_mut().poll_next(cx) {
+				Poll::Ready(Some(item)) => {
+					if this.run.first().is_some_and(|first| *first != item) {
+						return Poll::Ready(Some(mem::replace(this.run, alloc::vec![item])));
+					}
+					this.run.push(item);
+				}
+				Poll::Ready(None) => *this.done = true,
+				Poll::Pending => return Poll::Pending,
+			}
+		}
+		if this.run.is_empty() {
+			Poll::Ready(None)
+		} else {
+			Poll::Ready(Some(mem::take(this.run)))
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let buffered = !self.run.is_empty() as usize;
+		if self.done { return (buffered, Some(buffered)); }
+		let (lower, upper) = self.stream.size_hint();
+		(
+			(buffered + lower).min(1),
+			upper.and_then(|upper| upper.checked_add(buffered)),
+		)
+	}
+}
+
+impl<S: Stream> FusedStream for ConsGroupStream<S>
+where S::Item: Eq {
+	fn is_terminated(&self) -> bool {
+		self.done && self.run.is_empty()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::executor::block_on;
+	use futures::stream::{self, StreamExt};
+
+	#[test]
+	fn groups_stream_items() {
+		let runs: Vec<Vec<i32>> = block_on(stream::iter(vec![1, 1, 2, 3, 3, 3, 4, 5, 5]).cons_group().collect());
+		assert_eq!(runs, [vec![1, 1], vec![2], vec![3, 3, 3], vec![4], vec![5, 5]]);
+		let runs: Vec<Vec<i32>> = block_on(stream::empty().cons_group().collect());
+		assert!(runs.is_empty());
+	}
+
+	#[test]
+	fn waits_for_pending_items() {
+		let (sender, receiver) = futures::channel::mpsc::unbounded();
+		let mut runs = receiver.cons_group();
+		let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+		sender.unbounded_send('a').unwrap();
+		sender.unbounded_send('a').unwrap();
+		assert_eq!(runs.poll_next_unpin(&mut cx), Poll::Pending);
+		sender.unbounded_send('b').unwrap();
+		assert_eq!(runs.poll_next_unpin(&mut cx), Poll::Ready(Some(vec!['a', 'a'])));
+		assert_eq!(runs.poll_next_unpin(&mut cx), Poll::Pending);
+		drop(sender);
+		assert_eq!(runs.poll_next_unpin(&mut cx), Poll::Ready(Some(vec!['b'])));
+		assert_eq!(runs.poll_next_unpin(&mut cx), Poll::Ready(None));
+		assert!(runs.is_terminated());
+	}
+
+	#[test]
+	fn yields_sliding_windows() {
+		let windows: Vec<Vec<i32>> = block_on(
+			stream::iter(1..=4)
+				.each_cons(2)
+				.map(|window| window.iter().map(|item| **item).collect())
+				.collect(),
+		);
+		assert_eq!(windows, [[1, 2], [2, 3], [3, 4]]);
+		assert_eq!(stream::iter(0..10).each_cons(3).size_hint(), (8, Some(8)));
+		assert_eq!(block_on(stream::iter(0..2).each_cons(3).count()), 0);
+	}
+}
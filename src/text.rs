@@ -0,0 +1,180 @@
+//! Run grouping over string slices.
+
+use core::iter::FusedIterator;
+#[cfg(feature = "unicode-segmentation")]
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Add this into scope to give your strings the `cons_group()` method.
+///
+/// # Example
+///
+/// ```
+/// use each_cons::StrConsGroupExt;
+///
+/// let runs: Vec<&str> = "aaab  c".cons_group().collect();
+/// assert_eq!(runs, ["aaa", "b", "  ", "c"]);
+/// ```
+pub trait StrConsGroupExt {
+	/// Groups identical consecutive `char`s, the string counterpart of
+	/// [`ConsGroupExt::cons_group`].
+	///
+	/// [`ConsGroupExt::cons_group`]: crate::ConsGroupExt::cons_group
+	fn cons_group(&self) -> StrConsGroup<'_>;
+
+	/// Groups identical consecutive extended grapheme clusters, so that a
+	/// `char` followed by combining marks, or an emoji made of several
+	/// `char`s, is never split across runs.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use each_cons::StrConsGroupExt;
+	///
+	/// let text = "e\u{301}e\u{301}e";
+	/// let runs: Vec<&str> = text.cons_group_graphemes().collect();
+	/// assert_eq!(runs, ["e\u{301}e\u{301}", "e"]);
+	/// ```
+	#[cfg(feature = "unicode-segmentation")]
+	fn cons_group_graphemes(&self) -> GraphemeConsGroup<'_>;
+}
+
+impl StrConsGroupExt for str {
+	fn cons_group(&self) -> StrConsGroup<'_> {
+		StrConsGroup {
+			remaining: self,
+		}
+	}
+
+	#[cfg(feature = "unicode-segmentation")]
+	fn cons_group_graphemes(&self) -> GraphemeConsGroup<'_> {
+		GraphemeConsGroup {
+			remaining: self,
+		}
+	}
+}
+
+/// Iterator over runs of identical `char`s, see
+/// [`StrConsGroupExt::cons_group`].
+pub struct StrConsGroup<'a> {
+	remaining: &'a str,
+}
+
+impl<'a> Iterator for StrConsGroup<'a> {
+	type Item = &'a str;
+	fn next(&mut self) -> Option<Self::Item> {
+		let first = self.remaining.chars().next()?;
+		let len = self
+			.remaining
+			.char_indices()
+			.find(|&(_, c)| c != first)
+			.map_or(self.remaining.len(), |(i, _)| i);
+		let (run, remaining) = self.remaining.split_at(len);
+		self.remaining = remaining;
+		Some(run)
+	}
+}
+
+impl<'a> DoubleEndedIterator for StrConsGroup<'a> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let last = self.remaining.chars().next_back()?;
+		let start = self
+			.remaining
+			.char_indices()
+			.rev()
+			.find(|&(_, c)| c != last)
+			.map_or(0, |(i, c)| i + c.len_utf8());
+		let (remaining, run) = self.remaining.split_at(start);
+		self.remaining = remaining;
+		Some(run)
+	}
+}
+
+impl<'a> FusedIterator for StrConsGroup<'a> {}
+
+/// Iterator over runs of identical grapheme clusters, see
+/// [`StrConsGroupExt::cons_group_graphemes`].
+#[cfg(feature = "unicode-segmentation")]
+pub struct GraphemeConsGroup<'a> {
+	remaining: &'a str,
+}
+
+#[cfg(feature = "unicode-segmentation")]
+impl<'a> Iterator for GraphemeConsGroup<'a> {
+	type Item = &'a str;
+	fn next(&mut self) -> Option<Self::Item> {
+		let mut graphemes = self.remaining.grapheme_indices(true);
+		let (_, first) = graphemes.next()?;
+		let len = graphemes
+			.find(|&(_, g)| g != first)
+			.map_or(self.remaining.len(), |(i, _)| i);
+		let (run, remaining) = self.remaining.split_at(len);
+		self.remaining = remaining;
+		Some(run)
+	}
+}
+
+#[cfg(feature = "unicode-segmentation")]
+impl<'a> DoubleEndedIterator for GraphemeConsGroup<'a> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let mut graphemes = self.remaining.grapheme_indices(true).rev();
+		let (_, last) = graphemes.next()?;
+		let start = graphemes
+			.find(|&(_, g)| g != last)
+			.map_or(0, |(i, g)| i + g.len());
+		let (remaining, run) = self.remaining.split_at(start);
+		self.remaining = remaining;
+		Some(run)
+	}
+}
+
+#[cfg(feature = "unicode-segmentation")]
+impl<'a> FusedIterator for GraphemeConsGroup<'a> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn groups_identical_chars() {
+		let runs: Vec<_> = "aaaa  bcc\u{e9}\u{e9}".cons_group().collect();
+		assert_eq!(runs, ["aaaa", "  ", "b", "cc", "\u{e9}\u{e9}"]);
+		assert!("".cons_group().next().is_none());
+		let password = "hunter2222";
+		assert!(password.cons_group().any(|run| run.chars().count() >= 4));
+	}
+
+	#[test]
+	fn groups_chars_from_the_back() {
+		let text = "aab\u{1f600}\u{1f600}";
+		let runs: Vec<_> = text.cons_group().rev().collect();
+		assert_eq!(runs, ["\u{1f600}\u{1f600}", "b", "aa"]);
+		let mut runs = text.cons_group();
+		assert_eq!(runs.next(), Some("aa"));
+		assert_eq!(runs.next_back(), Some("\u{1f600}\u{1f600}"));
+		assert_eq!(runs.next_back(), Some("b"));
+		assert_eq!(runs.next(), None);
+	}
+
+	#[test]
+	fn splits_combining_marks_by_char() {
+		let runs: Vec<_> = "e\u{301}e\u{301}".cons_group().collect();
+		assert_eq!(runs, ["e", "\u{301}", "e", "\u{301}"]);
+	}
+
+	#[cfg(feature = "unicode-segmentation")]
+	#[test]
+	fn groups_identical_graphemes() {
+		let text = "e\u{301}e\u{301}ee\u{1f468}\u{200d}\u{1f469}\u{1f468}\u{200d}\u{1f469}!";
+		let runs: Vec<_> = text.cons_group_graphemes().collect();
+		assert_eq!(runs, [
+			"e\u{301}e\u{301}",
+			"ee",
+			"\u{1f468}\u{200d}\u{1f469}\u{1f468}\u{200d}\u{1f469}",
+			"!",
+		]);
+		let mut reversed: Vec<_> = text.cons_group_graphemes().rev().collect();
+		reversed.reverse();
+		assert_eq!(reversed, runs);
+		assert!("".cons_group_graphemes().next().is_none());
+	}
+}